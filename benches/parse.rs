@@ -0,0 +1,103 @@
+//! Measures the time taken by `Iban::parse`, the registry accessors, `calculate_checksum` and
+//! `Iban::rand` for IBAN formats from the shortest to the longest.
+//!
+//! Run with `cargo bench`. This uses a plain timing loop rather than a benchmark harness,
+//! so the results are only indicative, and best compared between runs on the same machine.
+
+// Benchmarks are not part of the library, so they may use `black_box` (stable since 1.66).
+#![allow(clippy::incompatible_msrv)]
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use arrayvec::ArrayString;
+use iban::Iban;
+
+const ITERATIONS: u32 = 1_000_000;
+
+const INPUTS: &[&str] = &[
+    "LC55HEMM000100010012001200023015",
+    "MT84MALT011000012345MTLCAST001S",
+    "QA58DOHB00001234567890ABCDEFG",
+    "BR1800360305000010009795493C1",
+    "FR1420041010050500013M02606",
+    "DE89370400440532013000",
+    "BE68539007547034",
+    "NO9386011117947",
+];
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    // Warm up, then keep the fastest of several runs to reduce noise.
+    f();
+    let fastest = (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..ITERATIONS {
+                f();
+            }
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or(Duration::ZERO);
+
+    println!("{name}: {:?} per iteration", fastest / ITERATIONS);
+}
+
+fn main() {
+    for input in INPUTS {
+        bench(&format!("parse {input}"), || {
+            black_box(Iban::parse(black_box(input))).expect("iban is valid");
+        });
+    }
+
+    for input in INPUTS {
+        let spaced = Iban::parse(input).expect("iban is valid").to_string();
+        bench(&format!("parse {spaced}"), || {
+            black_box(Iban::parse(black_box(&spaced))).expect("iban is valid");
+        });
+    }
+
+    let mut scratch = ArrayString::new();
+    for input in INPUTS {
+        bench(&format!("parse_into {input}"), || {
+            black_box(Iban::parse_into(black_box(input), &mut scratch)).expect("iban is valid");
+        });
+    }
+
+    // Each accessor looks up the country in the registry, which is not cached in `Iban`.
+    for input in INPUTS {
+        let iban = Iban::parse(input).expect("iban is valid");
+        bench(&format!("country_info {input}"), || {
+            black_box(black_box(iban).country_info());
+        });
+        bench(&format!("bank, branch and checksum {input}"), || {
+            let bban = black_box(iban).bban();
+            black_box((
+                bban.bank_identifier(),
+                bban.branch_identifier(),
+                bban.checksum(),
+            ));
+        });
+    }
+
+    for input in INPUTS {
+        bench(&format!("calculate_checksum {input}"), || {
+            black_box(iban::calculate_checksum(black_box(input.as_bytes())));
+        });
+    }
+
+    #[cfg(feature = "rand")]
+    {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
+        for input in INPUTS {
+            let country_code = &input[..2];
+            bench(&format!("rand {country_code}"), || {
+                black_box(Iban::rand(black_box(country_code), &mut rng)).expect("country is known");
+            });
+        }
+    }
+}
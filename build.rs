@@ -6,13 +6,13 @@ use regex::Regex;
 #[derive(Debug, serde::Deserialize)]
 struct Record {
     country_code: String,
-    //country_name: String,
+    country_name: String,
     //domestic_example: String,
     //bban_example: String,
     //bban_format_swift: String,
     //bban_format_regex: String,
     //bban_length: usize,
-    //iban_example: String,
+    iban_example: String,
     iban_format_swift: String,
     //iban_format_regex: String,
     iban_length: usize,
@@ -21,17 +21,46 @@ struct Record {
     bban_branchid_start_offset: Option<usize>,
     bban_branchid_stop_offset: Option<usize>,
     //registry_edition: String,
-    //country_sepa: String,
+    country_sepa: String,
     //swift_official: String,
     bban_checksum_start_offset: Option<usize>,
     bban_checksum_stop_offset: Option<usize>,
     //country_code_iana: String,
-    //country_code_iso3166_1_alpha2: String,
+    country_code_iso3166_1_alpha2: String,
     //parent_registrar: String,
     //currency_iso4217: String,
     //central_bank_url: String,
     //central_bank_name: String,
-    //membership: String,
+    membership: String,
+}
+
+/// Converts an inclusive `start..=stop` offset pair from the registry into an exclusive range.
+///
+/// Some registry entries record an empty segment with `stop < start` (e.g. NL's branch identifier),
+/// these are treated the same as a missing offset.
+///
+/// Panics if the segment extends past the end of the BBAN, as its accessor could never return it,
+/// except for a stop of exactly one past the end (see below).
+fn offset(
+    country_code: &str,
+    bban_length: usize,
+    start: Option<usize>,
+    stop: Option<usize>,
+) -> impl quote::ToTokens {
+    match (start, stop) {
+        (Some(start), Some(stop)) if stop >= start => {
+            // The registry records the two-digit key at the end of some BBANs (CF, CG, DJ, GA, GQ,
+            // KM, MA, TD and TG) with an inclusive stop one past the end, where FR (with the same
+            // layout) uses the last character. Such a stop is clamped to the end of the BBAN.
+            let stop = if stop == bban_length { stop - 1 } else { stop };
+            assert!(
+                stop < bban_length,
+                "offsets {start}..={stop} for {country_code} exceed the BBAN length of {bban_length}"
+            );
+            quote! { Some((#start, #stop + 1)) }
+        }
+        _ => quote! { None },
+    }
 }
 
 fn main() {
@@ -53,6 +82,8 @@ fn main() {
         .map(
             |Record {
                  country_code,
+                 country_name,
+                 iban_example,
                  iban_format_swift,
                  iban_length,
                  bban_bankid_start_offset,
@@ -61,9 +92,30 @@ fn main() {
                  bban_branchid_stop_offset,
                  bban_checksum_start_offset,
                  bban_checksum_stop_offset,
+                 country_sepa,
+                 country_code_iso3166_1_alpha2,
+                 membership,
              }| {
+                // Formats that are not fully covered by `pattern` are emitted without any
+                // characters, so that they are reported as unsupported rather than mis-validated.
+                let bban_format = &iban_format_swift[2..];
+                let covered = pattern
+                    .captures_iter(bban_format)
+                    .map(|captures| captures[0].len())
+                    .sum::<usize>();
+                let length = pattern
+                    .captures_iter(bban_format)
+                    .map(|captures| captures[1].parse::<usize>().unwrap())
+                    .sum::<usize>();
+                let supported = covered == bban_format.len() && length + 2 == iban_length;
+                if !supported {
+                    println!(
+                        "cargo:warning=unsupported format {iban_format_swift:?} for {country_code}"
+                    );
+                }
+
                 let captures = pattern
-                    .captures_iter(&iban_format_swift[2..])
+                    .captures_iter(bban_format)
                     .map(|captures| {
                         (
                             captures[1].parse::<usize>().unwrap(),
@@ -78,47 +130,59 @@ fn main() {
                         )
                     })
                     .map(|(len, char)| quote! { (#len, CharacterType::#char) });
-                let captures = iban_format_swift[..2]
-                    .as_bytes()
+                let captures = iban_format_swift.as_bytes()[..2]
                     .iter()
                     .map(|byte| (1usize, byte.to_ascii_uppercase()))
                     .map(|(len, char)| quote! { (#len, CharacterType::S(#char)) })
-                    .chain(captures);
+                    .chain(captures)
+                    .filter(|_| supported);
 
-                let bankid_offset = if let (Some(start), Some(end)) =
-                    (bban_bankid_start_offset, bban_bankid_stop_offset)
-                {
-                    quote! { Some((#start, #end + 1)) }
-                } else {
-                    quote! { None }
-                };
+                let bban_length = iban_length - 4;
+                let bankid_offset = offset(
+                    &country_code,
+                    bban_length,
+                    bban_bankid_start_offset,
+                    bban_bankid_stop_offset,
+                );
+                let branch_offset = offset(
+                    &country_code,
+                    bban_length,
+                    bban_branchid_start_offset,
+                    bban_branchid_stop_offset,
+                );
+                let checksum_offset = offset(
+                    &country_code,
+                    bban_length,
+                    bban_checksum_start_offset,
+                    bban_checksum_stop_offset,
+                );
 
-                let branch_offset = if let (Some(start), Some(end)) =
-                    (bban_branchid_start_offset, bban_branchid_stop_offset)
-                {
-                    quote! { Some((#start, #end + 1)) }
-                } else {
-                    quote! { None }
-                };
-
-                let checksum_offset = if let (Some(start), Some(end)) =
-                    (bban_checksum_start_offset, bban_checksum_stop_offset)
-                {
-                    quote! { Some((#start, #end + 1)) }
-                } else {
-                    quote! { None }
+                let sepa = country_sepa == "1";
+                // Registry-only codes (such as XK) have no ISO 3166-1 code.
+                let iso3166 = !country_code_iso3166_1_alpha2.is_empty();
+                let membership = match membership.as_str() {
+                    "eu_member" => format_ident!("EuMember"),
+                    "efta_member" => format_ident!("EftaMember"),
+                    "other_member" => format_ident!("OtherMember"),
+                    "non_member" => format_ident!("NonMember"),
+                    membership => panic!("unknown membership {membership:?} for {country_code}"),
                 };
 
                 (
                     country_code,
                     quote! {
-                        (
-                            #iban_length,
-                            &[#(#captures),*],
-                            #bankid_offset,
-                            #branch_offset,
-                            #checksum_offset,
-                        )
+                        CountryInfo {
+                            name: #country_name,
+                            length: #iban_length,
+                            format: &[#(#captures),*],
+                            bank_identifier: #bankid_offset,
+                            branch_identifier: #branch_offset,
+                            checksum: #checksum_offset,
+                            sepa: #sepa,
+                            iso3166: #iso3166,
+                            example: #iban_example,
+                            membership: Membership::#membership,
+                        }
                     },
                 )
             },
@@ -135,7 +199,7 @@ fn main() {
     std::fs::write(
         out_path.join("countries.rs"),
         format!(
-            "#[allow(clippy::type_complexity, clippy::unreadable_literal, clippy::identity_op)]\nstatic COUNTRIES: ::phf::Map<&'static str, (usize, &'static [(usize, CharacterType)], Option<(usize, usize)>, Option<(usize, usize)>, Option<(usize, usize)>)> = {countries};\n",
+            "#[allow(clippy::unreadable_literal, clippy::identity_op)]\nstatic COUNTRIES: ::phf::Map<&'static str, CountryInfo> = {countries};\n",
         ),
     )
     .expect("failed to write countries file");
@@ -0,0 +1,179 @@
+//! IBANs of which only the checksum is validated.
+
+use core::{fmt, ops::Deref};
+
+use arrayvec::ArrayString;
+
+use crate::{
+    bban_character_types, calculate_checksum, country_format, fmt_spaced, lookup_country, Case,
+    Iban, ParseError, IBAN_MAX_LENGTH,
+};
+
+/// Represents an IBAN of which only the checksum has been validated, as returned by
+/// [`Iban::parse_checksum_only`].
+///
+/// Unlike an [`Iban`], its country may be unknown, and its length and BBAN may not match the
+/// format of its country. It therefore only offers the parts that do not depend on the IBAN
+/// registry. Use [`ChecksumOnlyIban::to_iban`] to validate it fully.
+///
+/// Spaced formatting is obtained from the [`Display`](std::fmt::Display) implementation, and
+/// electronic formatting from the [`Debug`](std::fmt::Debug), [`Deref`](std::ops::Deref), or
+/// [`AsRef`](std::convert::AsRef) implementations, as for an [`Iban`].
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ChecksumOnlyIban(ArrayString<IBAN_MAX_LENGTH>);
+
+impl fmt::Debug for ChecksumOnlyIban {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl fmt::Display for ChecksumOnlyIban {
+    /// Spaced formatting of the `ChecksumOnlyIban`, as for an [`Iban`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_spaced(self, f)
+    }
+}
+
+impl Deref for ChecksumOnlyIban {
+    type Target = str;
+
+    /// Returns the electronic-format representation of the IBAN.
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0[..]
+    }
+}
+
+impl AsRef<str> for ChecksumOnlyIban {
+    /// Returns the electronic-format representation of the IBAN.
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl ChecksumOnlyIban {
+    /// Get the country code of the IBAN, which may not be in the IBAN registry.
+    #[inline]
+    #[must_use]
+    pub fn country_code(&self) -> &str {
+        &self[0..2]
+    }
+
+    /// Get the check digits of the IBAN.
+    #[inline]
+    #[must_use]
+    pub fn check_digits(&self) -> &str {
+        &self[2..4]
+    }
+
+    /// Get the BBAN of the IBAN, in electronic format.
+    ///
+    /// As the format of the BBAN is not validated, this is a string slice rather than a [`Bban`](crate::Bban).
+    #[inline]
+    #[must_use]
+    pub fn bban(&self) -> &str {
+        &self[4..]
+    }
+
+    /// Get the electronic format of the IBAN as a string slice.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Validate the IBAN against the format of its country.
+    ///
+    /// ```
+    /// let iban = iban::Iban::parse_checksum_only("DE89 3704 0044 0532 0130 00")?;
+    /// assert_eq!(iban.to_iban()?.bban().bank_identifier(), Some("37040044"));
+    ///
+    /// let iban = iban::Iban::parse_checksum_only("ZZ12ABC123")?;
+    /// assert_eq!(iban.to_iban(), Err(iban::ParseError::UnknownCountry));
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`], except for
+    /// `ParseError::WrongChecksum`, as the checksum is already known to be valid.
+    pub fn to_iban(&self) -> Result<Iban, ParseError> {
+        Iban::parse_strict(self)
+    }
+}
+
+impl Iban {
+    /// Parse a string as an IBAN, only validating its checksum.
+    ///
+    /// This is intended for IBANs whose BBAN format is not (yet) known to this crate, such as newly
+    /// assigned formats. The country code must be two ASCII letters, the check digits two ASCII
+    /// digits, and the remainder ASCII alphanumeric characters, of at most 34 characters in total.
+    /// Apart from that, only the checksum is validated.
+    ///
+    /// # Reduced guarantees
+    /// Unlike [`Iban::parse`], this does not check that:
+    /// * The country is known
+    /// * The IBAN has the expected length for its country
+    /// * The BBAN matches the format of its country
+    ///
+    /// The result is therefore a [`ChecksumOnlyIban`] rather than an `Iban`, which does not
+    /// offer the accessors that rely on the country's format (such as [`Iban::country_info`]
+    /// and [`Bban::bank_identifier`](crate::Bban::bank_identifier)).
+    ///
+    /// As with [`Iban::parse`], lowercase characters are only kept in the segments of a known
+    /// country's format that allow them, and converted to uppercase otherwise. An IBAN that
+    /// is also accepted by [`Iban::parse`] therefore has the same electronic format.
+    ///
+    /// ```
+    /// // A checksum-valid IBAN for a country that is not in the registry.
+    /// let iban = iban::Iban::parse_checksum_only("ZZ12ABC123")?;
+    /// assert_eq!(iban.bban(), "ABC123");
+    ///
+    /// assert!(iban::Iban::parse("ZZ12ABC123").is_err());
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns a `ParseError` for any of the following issues:
+    /// * Country code format issues (see: `ParseError::CountryCode`)
+    /// * Check digit format issues (see: `ParseError::CheckDigit`)
+    /// * Invalid characters (see: `ParseError::InvalidCharacter`)
+    /// * Longer than 34 characters (see: `ParseError::TooLong`)
+    /// * Checksum is wrong (see: `ParseError::WrongChecksum`)
+    pub fn parse_checksum_only(s: &str) -> Result<ChecksumOnlyIban, ParseError> {
+        let mut characters = s.bytes().filter(|byte| !byte.is_ascii_whitespace());
+        let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
+        Self::parse_prefix(&mut characters, Case::Preserve, &mut iban)?;
+
+        // Lowercase characters are kept where the country's format allows them, as with
+        // `Iban::parse`, so that an IBAN accepted by both is parsed the same way.
+        let format = lookup_country(&iban[..2])
+            .and_then(|country| country_format(country).ok())
+            .unwrap_or(&[]);
+        let mut character_types = bban_character_types(format);
+
+        for ch in characters {
+            if !ch.is_ascii_alphanumeric() {
+                return Err(ParseError::InvalidCharacter);
+            }
+
+            let ch = character_types
+                .next()
+                .map_or(ch.to_ascii_uppercase(), |character_type| {
+                    Case::Preserve.apply(character_type, ch)
+                });
+            iban.try_push(char::from(ch))
+                .map_err(|_| ParseError::TooLong {
+                    expected: IBAN_MAX_LENGTH,
+                })?;
+        }
+
+        if calculate_checksum(iban.as_bytes()) != 1 {
+            return Err(ParseError::WrongChecksum);
+        }
+
+        Ok(ChecksumOnlyIban(iban))
+    }
+}
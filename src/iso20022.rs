@@ -0,0 +1,77 @@
+//! Helpers for IBANs in ISO 20022 messages, such as `pain.001` and `camt.053`.
+//!
+//! ISO 20022 messages carry IBANs as the text of `<IBAN>` elements, for example in
+//! `Document/CstmrCdtTrfInitn/PmtInf/DbtrAcct/Id/IBAN`. These helpers do not parse XML
+//! themselves, they validate text extracted by an XML parser and write the electronic
+//! format expected by the `IBAN2007Identifier` type.
+
+use core::fmt;
+
+use crate::{Iban, ParseError};
+
+/// The name of the element that contains an IBAN in ISO 20022 messages.
+pub const ELEMENT_NAME: &str = "IBAN";
+
+/// An error that occurred while parsing the text of an ISO 20022 element as an IBAN.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ElementError<'a> {
+    /// The path of the element that contains the invalid IBAN.
+    pub path: &'a str,
+    /// The error that occurred while parsing the IBAN.
+    pub error: ParseError,
+}
+
+impl fmt::Display for ElementError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid IBAN in <{}>: {}", self.path, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ElementError<'_> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Parse the text of an ISO 20022 element as an Iban.
+///
+/// The `path` of the element is only used to report errors, so that invalid IBANs can be
+/// located in the message. Surrounding whitespace, as left by pretty-printed XML, is ignored.
+///
+/// ```
+/// let iban = iban::iso20022::parse_element("DbtrAcct/Id/IBAN", "\n  DE89370400440532013000\n")?;
+/// assert_eq!(iban.as_str(), "DE89370400440532013000");
+///
+/// let err = iban::iso20022::parse_element("CdtrAcct/Id/IBAN", "DE88370400440532013000").unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "invalid IBAN in <CdtrAcct/Id/IBAN>: checksum validation failed (check digits are wrong)"
+/// );
+/// # Ok::<(), iban::iso20022::ElementError>(())
+/// ```
+///
+/// # Errors
+/// This function returns the same errors as [`Iban::parse`], along with the `path` of the element.
+pub fn parse_element<'a>(path: &'a str, text: &str) -> Result<Iban, ElementError<'a>> {
+    Iban::parse(text).map_err(|error| ElementError { path, error })
+}
+
+/// Write an IBAN as an ISO 20022 `<IBAN>` element.
+///
+/// The IBAN is written in electronic format, as required by the `IBAN2007Identifier` type.
+///
+/// ```
+/// let iban: iban::Iban = "DE89 3704 0044 0532 0130 00".parse()?;
+///
+/// let mut xml = String::new();
+/// iban::iso20022::write_element(&mut xml, &iban).unwrap();
+/// assert_eq!(xml, "<IBAN>DE89370400440532013000</IBAN>");
+/// # Ok::<(), iban::ParseError>(())
+/// ```
+///
+/// # Errors
+/// This function returns an error if writing to `writer` fails.
+pub fn write_element<W: fmt::Write>(writer: &mut W, iban: &Iban) -> fmt::Result {
+    write!(writer, "<{ELEMENT_NAME}>{}</{ELEMENT_NAME}>", iban.as_str())
+}
@@ -3,17 +3,137 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 #![warn(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::{fmt, ops::Deref, str::FromStr};
 
 use arrayvec::ArrayString;
 
+mod checksum_only;
+#[cfg(feature = "iso20022")]
+pub mod iso20022;
+mod national;
+mod ocr;
+#[cfg(feature = "alloc")]
+mod packed;
 mod util;
-use util::{digits, ChunksExt as _, IteratorExt as _};
+pub use checksum_only::ChecksumOnlyIban;
+pub use ocr::parse_ocr;
+use util::{
+    digits, is_default_ignorable, to_halfwidth, ChunksExt as _, IteratorExt as _, PercentDecode,
+};
+
+/// A country's entry in the IBAN registry, as returned by [`Iban::country_info`].
+///
+/// Offsets are `(start, end)` pairs of byte offsets into the BBAN, where `start` is inclusive
+/// and `end` is exclusive, so that they can be used to slice [`Bban::as_str`].
+///
+/// ```
+/// use iban::{CharacterType, Iban};
+///
+/// let iban: Iban = "GB82 WEST 1234 5698 7654 32".parse()?;
+/// let info = iban.country_info();
+///
+/// assert_eq!(info.name, "United Kingdom");
+/// assert_eq!(info.length, 22);
+/// assert_eq!(info.bank_identifier, Some((0, 4)));
+/// assert_eq!(&iban.bban()[0..4], "WEST");
+/// # Ok::<(), iban::ParseError>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CountryInfo {
+    /// The name of the country, as given in the IBAN registry.
+    pub name: &'static str,
+    /// The length of the country's IBANs.
+    pub length: usize,
+    /// The format of the country's IBANs, as `(count, character type)` runs.
+    ///
+    /// This includes the country code (as [`CharacterType::S`]) and check digits. It is empty if
+    /// the format could not be fully parsed from the registry, in which case IBANs of the country
+    /// are rejected with [`ParseError::UnsupportedFormat`].
+    pub format: &'static [(usize, CharacterType)],
+    /// The offsets of the bank identifier within the BBAN (if it has one).
+    pub bank_identifier: Option<(usize, usize)>,
+    /// The offsets of the branch identifier within the BBAN (if it has one).
+    pub branch_identifier: Option<(usize, usize)>,
+    /// The offsets of the national check digits within the BBAN (if it has them).
+    pub checksum: Option<(usize, usize)>,
+    sepa: bool,
+    iso3166: bool,
+    example: &'static str,
+    membership: Membership,
+}
 
 include!(concat!(env!("OUT_DIR"), "/countries.rs"));
 
+/// Returns the format of a country's IBANs.
+///
+/// Returns `ParseError::UnsupportedFormat` if the format could not be fully parsed from the
+/// registry by the build script, such as formats with variable-length segments.
+fn country_format(country: &CountryInfo) -> Result<&'static [(usize, CharacterType)], ParseError> {
+    if country.format.is_empty() {
+        return Err(ParseError::UnsupportedFormat);
+    }
+
+    Ok(country.format)
+}
+
+/// Looks up a country in the IBAN registry by its (case-insensitive) country code.
+fn lookup_country(country_code: &str) -> Option<&'static CountryInfo> {
+    match *country_code.as_bytes() {
+        [first, second] => {
+            let country_code = [first.to_ascii_uppercase(), second.to_ascii_uppercase()];
+            COUNTRIES.get(core::str::from_utf8(&country_code).ok()?)
+        }
+        _ => None,
+    }
+}
+
 const IBAN_MAX_LENGTH: usize = 34;
 
+/// The maximum length of an IBAN in print format, with a space after every four characters.
+const IBAN_MAX_SPACED_LENGTH: usize = IBAN_MAX_LENGTH + (IBAN_MAX_LENGTH - 1) / 4;
+
+/// Writes `s` with a space after every four characters, respecting the formatter's options.
+///
+/// The options (such as width, fill and alignment) apply to the spaced string as a whole.
+fn fmt_spaced(s: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut spaced = ArrayString::<IBAN_MAX_SPACED_LENGTH>::new();
+    for chunk in s.chunks::<4>().delimited(" ") {
+        spaced.push_str(chunk);
+    }
+
+    f.pad(&spaced)
+}
+
+/// Commonly mistaken country codes, along with the country code that was likely intended.
+///
+/// See [`suggest_country`].
+const COUNTRY_CODE_SUGGESTIONS: &[(&str, &str)] = &[
+    // EL is used for Greece by the European Union, but its ISO 3166 code is GR.
+    ("EL", "GR"),
+    // UK is commonly used for the United Kingdom, but its ISO 3166 code is GB.
+    ("UK", "GB"),
+];
+
+/// Countries that zero-pad their numeric bank identifier to a fixed width.
+///
+/// See [`Bban::bank_identifier_trimmed`].
+const ZERO_PADDED_BANK_IDENTIFIERS: &[&str] = &["AD", "CH", "CY", "GR", "LI", "LU"];
+
+/// Countries whose IBAN scheme has been withdrawn, but which are kept so that existing IBANs can
+/// still be parsed.
+///
+/// No country in the bundled registry has been withdrawn yet. The IIBAN code (AA) is only
+/// listed in tests, to exercise [`Iban::is_deprecated_country`].
+///
+/// See [`Iban::is_deprecated_country`].
+const DEPRECATED_COUNTRIES: &[&str] = &[
+    #[cfg(test)]
+    "AA",
+];
+
 /// Represents an IBAN.
 ///
 /// A valid International Bank Account Number (IBAN) is a bank account number that is internationally
@@ -43,6 +163,10 @@ pub struct Iban(ArrayString<IBAN_MAX_LENGTH>);
 /// If the BBAN does not contain a bank identifier, branch identifier or checksum, the respective methods will return None.
 ///
 /// Use [`Iban::bban`] to obtain this.
+///
+/// With the `serde` feature, a `Bban` is serialized as the electronic format of the BBAN only,
+/// without the country code and check digits of its IBAN. It cannot be deserialized, as the
+/// country is needed to validate it, deserialize an [`Iban`] instead.
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Bban(ArrayString<IBAN_MAX_LENGTH>);
 
@@ -62,29 +186,33 @@ impl fmt::Debug for Bban {
 
 impl fmt::Display for Iban {
     /// Spaced formatting of the `Iban`.
+    ///
+    /// The width, fill and alignment options apply to the spaced string as a whole, so the
+    /// inserted spaces count towards the width. The longest IBANs are 42 characters wide with
+    /// spaces, so `{:<42}` aligns any IBAN in a table column.
+    ///
+    /// ```
+    /// let iban: iban::Iban = "DE89370400440532013000".parse()?;
+    /// assert_eq!(format!("[{iban:<30}]"), "[DE89 3704 0044 0532 0130 00   ]");
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for chunk in self.as_ref().chunks::<4>().delimited(" ") {
-            write!(f, "{chunk}")?;
-        }
-
-        Ok(())
+        fmt_spaced(self, f)
     }
 }
 
 impl fmt::Display for Bban {
     /// Spaced formatting of the `Bban`.
+    ///
+    /// As for [`Iban`], the width, fill and alignment options apply to the spaced string.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for chunk in self.as_ref().chunks::<4>().delimited(" ") {
-            write!(f, "{chunk}")?;
-        }
-
-        Ok(())
+        fmt_spaced(self, f)
     }
 }
 
 /// Represents the type of a character in an IBAN.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum CharacterType {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CharacterType {
     /// Digits (numeric characters 0 to 9 only)
     N,
     /// Upper case letters (alphabetic characters A-Z only)
@@ -102,15 +230,55 @@ enum CharacterType {
     S(u8),
 }
 
+/// Character class bit of ASCII digits, see [`CHARACTER_CLASSES`].
+const DIGIT: u8 = 1 << 0;
+/// Character class bit of ASCII upper case letters, see [`CHARACTER_CLASSES`].
+const UPPER: u8 = 1 << 1;
+/// Character class bit of ASCII lower case letters, see [`CHARACTER_CLASSES`].
+const LOWER: u8 = 1 << 2;
+
+/// The character class bits of every byte, so that [`CharacterType::contains`] is a single lookup.
+const CHARACTER_CLASSES: [u8; 256] = {
+    let mut classes = [0; 256];
+    let mut ch = 0;
+    while ch < 256 {
+        #[allow(clippy::cast_possible_truncation)]
+        let byte = ch as u8;
+        classes[ch] = if byte.is_ascii_digit() {
+            DIGIT
+        } else if byte.is_ascii_uppercase() {
+            UPPER
+        } else if byte.is_ascii_lowercase() {
+            LOWER
+        } else {
+            0
+        };
+        ch += 1;
+    }
+    classes
+};
+
 impl CharacterType {
     /// Returns true if `ch` is a member of the character type `self`.
     pub const fn contains(self, ch: u8) -> bool {
+        let mask = match self {
+            CharacterType::N => DIGIT,
+            CharacterType::A => UPPER,
+            CharacterType::C => DIGIT | UPPER | LOWER,
+            CharacterType::I => DIGIT | UPPER,
+            CharacterType::S(expected) => return ch == expected,
+        };
+        CHARACTER_CLASSES[ch as usize] & mask != 0
+    }
+
+    /// Returns a short description of the characters in the character type `self`.
+    const fn description(self) -> &'static str {
         match self {
-            CharacterType::N => ch.is_ascii_digit(),
-            CharacterType::A => ch.is_ascii_uppercase(),
-            CharacterType::C => ch.is_ascii_alphanumeric(),
-            CharacterType::I => ch.is_ascii_uppercase() || ch.is_ascii_digit(),
-            CharacterType::S(expected) => ch == expected,
+            CharacterType::N => "a digit",
+            CharacterType::A => "an upper case letter",
+            CharacterType::C => "an alphanumeric character",
+            CharacterType::I => "an upper case alphanumeric character",
+            CharacterType::S(_) => "a specific character",
         }
     }
 
@@ -145,6 +313,99 @@ impl CharacterType {
     }
 }
 
+/// How lowercase characters are handled while parsing an IBAN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Case {
+    /// Characters in segments that allow lowercase ([`CharacterType::C`]) keep their case, all
+    /// other characters (including [`CharacterType::I`] segments) are converted to uppercase.
+    Preserve,
+    /// No characters are converted, so lowercase characters are only accepted in
+    /// segments that allow them ([`CharacterType::C`]).
+    Strict,
+    /// All characters are converted to uppercase, including those in [`CharacterType::C`]
+    /// segments.
+    Upper,
+}
+
+impl Case {
+    /// Converts `ch` as required for a BBAN character of type `character_type`.
+    fn apply(self, character_type: CharacterType, ch: u8) -> u8 {
+        match self {
+            Case::Preserve if character_type == CharacterType::C => ch,
+            Case::Strict => ch,
+            Case::Preserve | Case::Upper => ch.to_ascii_uppercase(),
+        }
+    }
+}
+
+/// Expands a country's run-length encoded format into the type of each BBAN character.
+///
+/// The first four entries (country code and check digits) are skipped.
+fn bban_character_types(
+    format: &[(usize, CharacterType)],
+) -> impl Iterator<Item = CharacterType> + '_ {
+    format
+        .iter()
+        .flat_map(|(count, character_type)| (0..*count).map(move |_| character_type))
+        .skip(4)
+        .copied()
+}
+
+/// Check if the BBAN position is part of the country's bank or branch identifier.
+fn is_identifier(country: &CountryInfo, position: usize) -> bool {
+    [country.bank_identifier, country.branch_identifier]
+        .iter()
+        .flatten()
+        .any(|&(start, end)| (start..end).contains(&position))
+}
+
+/// Returns the offsets of the first segment of a BBAN format.
+///
+/// Returns `None` if the BBAN consists of a single segment, or the format is unsupported.
+fn first_bban_segment(format: &[(usize, CharacterType)]) -> Option<(usize, usize)> {
+    // The first three entries are the two letters of the country code and the check digits.
+    match format.get(3..)? {
+        [(count, _), _, ..] => Some((0, *count)),
+        _ => None,
+    }
+}
+
+/// The membership of a country in the European Union (EU) or the European Free Trade Association (EFTA).
+///
+/// This reflects the `membership` column of the IBAN registry. Note that EFTA membership does not
+/// imply membership of the European Economic Area (EEA), as Switzerland is not part of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Membership {
+    /// A member of the European Union.
+    EuMember,
+    /// A member of the European Free Trade Association.
+    EftaMember,
+    /// A country with another relationship to the EU, such as the United Kingdom or Monaco.
+    OtherMember,
+    /// A country that is not a member of the EU or EFTA.
+    NonMember,
+}
+
+/// How strictly an IBAN string is parsed by [`Iban::parse_level`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Strictness {
+    /// Accepts IBANs as they are commonly copied from documents and web pages.
+    ///
+    /// This behaves like [`Iban::parse_tolerant`], so Unicode whitespace and invisible
+    /// formatting characters are discarded. A leading `IBAN` prefix (in any case, and optionally
+    /// followed by a `:`), as printed on invoices and statements, is also discarded.
+    Lenient,
+    /// Accepts the electronic and print formats, in any case, as with [`Iban::parse`].
+    ///
+    /// ASCII whitespace is discarded, and letters are converted to uppercase, except in
+    /// alphanumeric BBAN segments.
+    Standard,
+    /// Only accepts the electronic format, as with [`Iban::parse_strict`].
+    ///
+    /// Whitespace and lowercase letters (except in segments that allow them) are rejected.
+    Strict,
+}
+
 /// An error that can occur when parsing an IBAN string.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum ParseError {
@@ -158,32 +419,168 @@ pub enum ParseError {
     ///
     /// If you're sure that it should be known, please open an issue.
     UnknownCountry,
-    /// The length of the IBAN does not match the expected length for the country.
-    InvalidLength,
+    /// The country is known, but the format of its IBANs is not supported.
+    ///
+    /// This is returned for countries whose format in the IBAN registry could not be parsed,
+    /// such as formats with variable-length segments.
+    UnsupportedFormat,
+    /// The IBAN is shorter than the expected length for the country.
+    TooShort {
+        /// The expected length of the IBAN for the country.
+        expected: usize,
+    },
+    /// The IBAN is longer than the expected length for the country.
+    TooLong {
+        /// The expected length of the IBAN for the country.
+        expected: usize,
+    },
     /// The format of the BBAN does not match the expected format for the country.
-    InvalidBban,
+    InvalidBban {
+        /// The position of the first invalid character in the electronic format of the IBAN.
+        position: usize,
+        /// The type of character that was expected at that position.
+        expected: CharacterType,
+    },
     /// The calculated checksum of the IBAN is invalid.
     WrongChecksum,
+    /// Several corrections of the input are valid IBANs, so the intended one is unknown.
+    ///
+    /// This is only returned by [`parse_ocr`].
+    Ambiguous,
+    /// Not enough distinct IBANs could be generated for the country.
+    ///
+    /// This is only returned by `Iban::rand_batch`.
+    Exhausted,
 }
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::CountryCode => "invalid country code",
-            Self::CheckDigit => "invalid check digit",
-            Self::InvalidCharacter => "invalid character",
-            Self::UnknownCountry => "unknown country",
-            Self::InvalidLength => "invalid length",
-            Self::InvalidBban => "invalid bban",
-            Self::WrongChecksum => "checksum validation failed",
+            Self::CountryCode => "invalid country code (expected two ASCII letters)".fmt(f),
+            Self::CheckDigit => "invalid check digits (expected two ASCII digits)".fmt(f),
+            Self::InvalidCharacter => {
+                "invalid character (expected ASCII letters and digits)".fmt(f)
+            }
+            Self::UnknownCountry => "unknown country code".fmt(f),
+            Self::UnsupportedFormat => {
+                "unsupported format (the country is known, but its format cannot be validated)"
+                    .fmt(f)
+            }
+            Self::TooShort { expected } => write!(f, "too short (expected length {expected})"),
+            Self::TooLong { expected } => write!(f, "too long (expected length {expected})"),
+            Self::InvalidBban { position, expected } => write!(
+                f,
+                "invalid bban (expected {} at position {position})",
+                expected.description()
+            ),
+            Self::WrongChecksum => "checksum validation failed (check digits are wrong)".fmt(f),
+            Self::Ambiguous => "ambiguous (several corrections are valid)".fmt(f),
+            Self::Exhausted => "exhausted (not enough distinct IBANs could be generated)".fmt(f),
         }
-        .fmt(f)
     }
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for ParseError {}
 
+/// A hint about the likely cause of a [`ParseError`], as reported by [`Iban::parse_verbose`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Hint {
+    /// The input is empty, or only contains whitespace and other ignored characters.
+    ///
+    /// This is reported along with `ParseError::CountryCode`, and can be used to tell a field
+    /// that was left blank apart from a malformed country code.
+    Empty,
+    /// The country code and check digits appear to be swapped (e.g. `89DE...` instead of `DE89...`).
+    ///
+    /// Contains the IBAN with the country code and check digits in the correct order.
+    SwappedCountryAndCheckDigits(Iban),
+    /// The country code appears to be duplicated (e.g. `DEDE89...` instead of `DE89...`).
+    ///
+    /// Contains the IBAN with a single country code.
+    DuplicatedCountryCode(Iban),
+    /// The IBAN does not have the expected length for its country.
+    ///
+    /// Comparing `length` to `expected` tells whether characters were dropped or added.
+    WrongLength {
+        /// The country code detected from the first two characters.
+        country_code: &'static str,
+        /// The expected length of an IBAN for the country.
+        expected: usize,
+        /// The length of the input, excluding whitespace and other ignored characters.
+        length: usize,
+    },
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => "the input is empty".fmt(f),
+            Self::SwappedCountryAndCheckDigits(iban) => write!(
+                f,
+                "country code and check digits may be swapped (did you mean {iban}?)"
+            ),
+            Self::DuplicatedCountryCode(iban) => {
+                write!(f, "country code may be duplicated (did you mean {iban}?)")
+            }
+            Self::WrongLength {
+                country_code,
+                expected,
+                length,
+            } => write!(
+                f,
+                "{country_code} IBANs have {expected} characters, but {length} were given"
+            ),
+        }
+    }
+}
+
+/// A successfully parsed IBAN, along with details about its country and the input, as returned
+/// by [`Iban::parse_detailed`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ParsedIban {
+    /// The parsed IBAN.
+    pub iban: Iban,
+    /// The name of the IBAN's country, as given in the IBAN registry.
+    pub country_name: &'static str,
+    /// Whether the IBAN's country is within the scope of SEPA (see: [`Iban::is_sepa`]).
+    pub sepa: bool,
+    /// Whether the input had to be normalized (see: [`parse_and_report`]).
+    pub normalized: bool,
+}
+
+/// An error returned by [`Iban::parse_verbose`], along with a hint about its likely cause.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Diagnostic {
+    /// The error that occurred while parsing.
+    pub error: ParseError,
+    /// A hint about the likely cause of the error, if one could be determined.
+    pub hint: Option<Hint>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.hint {
+            Some(hint) => write!(f, "{}: {hint}", self.error),
+            None => self.error.fmt(f),
+        }
+    }
+}
+
+impl From<Diagnostic> for ParseError {
+    #[inline]
+    fn from(diagnostic: Diagnostic) -> Self {
+        diagnostic.error
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
 impl Deref for Iban {
     type Target = str;
 
@@ -270,6 +667,33 @@ impl AsRef<str> for Bban {
     }
 }
 
+impl PartialEq<ArrayString<IBAN_MAX_LENGTH>> for Iban {
+    /// Compares the electronic-format representation of the `Iban` to a buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iban::Iban;
+    ///
+    /// let iban: Iban = "DE89 3704 0044 0532 0130 00".parse().unwrap();
+    /// let buffer = iban.into_inner();
+    ///
+    /// assert!(iban == buffer);
+    /// ```
+    #[inline]
+    fn eq(&self, other: &ArrayString<IBAN_MAX_LENGTH>) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<Iban> for ArrayString<IBAN_MAX_LENGTH> {
+    /// Compares a buffer to the electronic-format representation of the `Iban`.
+    #[inline]
+    fn eq(&self, other: &Iban) -> bool {
+        *self == other.0
+    }
+}
+
 impl FromStr for Iban {
     type Err = ParseError;
 
@@ -279,70 +703,10 @@ impl FromStr for Iban {
     /// an `Iban` instance with the same value as the parsed string. Otherwise, it returns a
     /// [`ParseError`] indicating the reason for the failure.
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
-        let mut characters = value
-            .as_bytes()
-            .iter()
-            .copied()
-            .filter(|byte| !byte.is_ascii_whitespace())
-            .map(|b| b.to_ascii_uppercase());
-
-        for _ in 0..2 {
-            let ch = characters
-                .next()
-                .filter(u8::is_ascii_uppercase)
-                .ok_or(ParseError::CountryCode)?;
-            iban.push(char::from(ch));
-        }
-
-        for _ in 0..2 {
-            let ch = characters
-                .next()
-                .filter(u8::is_ascii_digit)
-                .ok_or(ParseError::CheckDigit)?;
-            iban.push(char::from(ch));
-        }
-
-        let country_code = &iban[..2];
-        let &(expected_length, validation, ..) = COUNTRIES
-            .get(country_code)
-            .ok_or(ParseError::UnknownCountry)?;
-
-        let mut validation = validation
-            .iter()
-            .flat_map(|(count, character_type)| (0..*count).map(move |_| character_type))
-            .skip(4)
-            .copied();
-
-        for ch in characters {
-            if !ch.is_ascii_alphanumeric() {
-                return Err(ParseError::InvalidCharacter);
-            }
-
-            // Validation must have at least as many characters as input.
-            let character_type = validation.next().ok_or(ParseError::InvalidLength)?;
-            if !character_type.contains(ch) {
-                return Err(ParseError::InvalidBban);
-            }
-
-            iban.try_push(char::from(ch))
-                .map_err(|_| ParseError::InvalidLength)?;
-        }
-
-        // Input must have at least as many characters as validation.
-        if validation.next().is_some() {
-            return Err(ParseError::InvalidLength);
-        }
-
-        // Since we check validation.len() >= input.len() and input.len() >= validation.len(),
-        // we know that they are equal and this should be impossible.
-        debug_assert_eq!(expected_length, iban.len());
-
-        if calculate_checksum(iban.as_bytes()) != 1 {
-            return Err(ParseError::WrongChecksum);
-        }
-
-        Ok(Self(iban))
+        Self::parse_bytes(
+            value.bytes().filter(|byte| !byte.is_ascii_whitespace()),
+            Case::Preserve,
+        )
     }
 }
 
@@ -356,6 +720,37 @@ impl Iban {
         &self[0..2]
     }
 
+    /// Check if the IBAN belongs to the given country.
+    ///
+    /// The country code is compared case-insensitively, so `"de"` matches a German IBAN.
+    #[inline]
+    #[must_use]
+    pub fn matches_country(&self, country_code: &str) -> bool {
+        self.country_code().eq_ignore_ascii_case(country_code)
+    }
+
+    /// Check if two IBANs identify the same account, ignoring their check digits.
+    ///
+    /// The country codes and BBANs are compared, but the check digits are not. Since check
+    /// digits are only unique modulo 97, the same account can have two valid IBANs, such as
+    /// `DE02370400440532010007` and `DE99370400440532010007`.
+    ///
+    /// ```
+    /// use iban::Iban;
+    ///
+    /// let a: Iban = "DE02370400440532010007".parse()?;
+    /// let b: Iban = "DE99370400440532010007".parse()?;
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.same_account(&b));
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn same_account(&self, other: &Iban) -> bool {
+        self.country_code() == other.country_code() && self.bban().as_str() == other.bban().as_str()
+    }
+
     /// Get the check digits of the IBAN.
     ///
     /// Returns a string slice containing the two check digits immediately following the country code.
@@ -383,85 +778,1160 @@ impl Iban {
         self
     }
 
-    /// Parse a string as an Iban.
-    ///
-    /// This method attempts to parse a string as an `Iban`. It returns a `Result`
-    /// containing the parsed `Iban` if successful, or a [`ParseError`] if the string
-    /// could not be parsed as an `Iban`.
+    /// Get the IBAN as a byte slice.
     ///
-    /// # Errors
-    /// This method returns a `ParseError` for any of the following issues:
-    /// * Country code format issues (see: `ParseError::CountryCode`)
-    /// * Check digit format issues (see: `ParseError::CheckDigit`)
-    /// * Invalid characters (see: `ParseError::InvalidCharacter`)
-    /// * Over maximum IBAN length (see: `ParseError::TooLong`)
-    /// * Unknown country (see: `ParseError::UnknownCountry`)
-    /// * Invalid length (see: `ParseError::InvalidLength`)
-    /// * Invalid BBAN format (see: `ParseError::InvalidBban`)
-    /// * Checksum is wrong (see: `ParseError::WrongChecksum`)
+    /// Returns the bytes of the electronic-format representation of the IBAN, for example to
+    /// feed it to a hash function.
     #[inline]
-    pub fn parse(s: &str) -> Result<Self, ParseError> {
-        FromStr::from_str(s)
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
     }
 
-    /// Generates a random IBAN for the specified `country_code` using the given `rng`.
+    /// Check if the IBAN's country is within the scope of the Single Euro Payments Area (SEPA).
     ///
-    /// # Returns
-    /// If successful, returns an `Iban` instance representing the generated IBAN.
+    /// Returns `true` if the country participates in the SEPA schemes according to the IBAN registry.
+    /// This includes several countries outside of the EU, such as Switzerland, Norway and the United Kingdom.
     ///
-    /// # Errors
-    /// Returns a `ParseError` if the specified `country_code` is invalid or unknown.
-    #[cfg(feature = "rand")]
-    pub fn rand<R: ?Sized + rand::Rng>(
-        country_code: &str,
-        rng: &mut R,
-    ) -> Result<Self, ParseError> {
-        let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
-        let mut country_code = country_code.as_bytes().iter().map(u8::to_ascii_uppercase);
+    /// This reflects participation at the country level only, it does not guarantee that the bank
+    /// holding this specific account is reachable through SEPA.
+    #[inline]
+    #[must_use]
+    pub fn is_sepa(&self) -> bool {
+        self.country_info().sepa
+    }
 
-        for _ in 0..2 {
-            let ch = country_code
-                .next()
-                .filter(u8::is_ascii_uppercase)
-                .ok_or(ParseError::CountryCode)?;
-            iban.push(char::from(ch));
-        }
+    /// Check if the IBAN's country scheme has been withdrawn from the IBAN registry.
+    ///
+    /// Withdrawn countries are kept, so that IBANs that are already stored can still be parsed,
+    /// but callers may want to flag them for review. No country in the bundled registry is
+    /// currently withdrawn, so this returns `false` for every IBAN.
+    #[inline]
+    #[must_use]
+    pub fn is_deprecated_country(&self) -> bool {
+        DEPRECATED_COUNTRIES.contains(&self.country_code())
+    }
 
-        if country_code.next().is_some() || iban.len() != 2 {
-            return Err(ParseError::UnknownCountry);
-        }
+    /// Get the membership of the IBAN's country, as recorded in the IBAN registry.
+    ///
+    /// ```
+    /// use iban::{Iban, Membership};
+    ///
+    /// let iban: Iban = "NO9386011117947".parse()?;
+    /// assert_eq!(iban.membership(), Membership::EftaMember);
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn membership(&self) -> Membership {
+        self.country_info().membership
+    }
 
-        iban.push_str("00");
+    /// Get the registry entry of the IBAN's country.
+    #[inline]
+    #[must_use]
+    pub fn country_info(&self) -> &'static CountryInfo {
+        COUNTRIES
+            .get(self.country_code())
+            .expect("a valid IBAN has a known country")
+    }
 
-        let &(expected_length, validation, ..) = COUNTRIES
-            .get(&iban[..2])
-            .ok_or(ParseError::UnknownCountry)?;
+    /// Check if the IBAN's country is a member of the European Union (EU).
+    ///
+    /// This is based on the IBAN registry, which also records the EU's outermost regions that have
+    /// their own country code (such as French Guiana and Réunion) as members.
+    #[inline]
+    #[must_use]
+    pub fn is_eu(&self) -> bool {
+        self.membership() == Membership::EuMember
+    }
+
+    /// Get the number of groups in the spaced formatting of the IBAN.
+    ///
+    /// The spaced formatting (see [`Display`](std::fmt::Display)) splits the IBAN into groups of
+    /// four characters, where only the last group may be shorter.
+    ///
+    /// ```
+    /// let iban: iban::Iban = "DE89370400440532013000".parse()?;
+    /// assert_eq!(iban.to_string(), "DE89 3704 0044 0532 0130 00");
+    /// assert_eq!(iban.group_count(), 6);
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn group_count(&self) -> usize {
+        (self.0.len() + 3) / 4
+    }
+
+    /// Get the length of the last group in the spaced formatting of the IBAN.
+    ///
+    /// This is between one and four characters, see [`Iban::group_count`].
+    ///
+    /// ```
+    /// let iban: iban::Iban = "DE89370400440532013000".parse()?;
+    /// assert_eq!(iban.to_string(), "DE89 3704 0044 0532 0130 00");
+    /// assert_eq!(iban.last_group_len(), 2);
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn last_group_len(&self) -> usize {
+        self.0.len() - (self.group_count() - 1) * 4
+    }
+
+    /// Get the positions of the spaces in the spaced formatting of the IBAN.
+    ///
+    /// Yields the byte index of each space that separates two groups in the
+    /// [`Display`](std::fmt::Display) output, which is every fifth character.
+    ///
+    /// ```
+    /// let iban: iban::Iban = "DE89370400440532013000".parse()?;
+    /// assert_eq!(iban.to_string(), "DE89 3704 0044 0532 0130 00");
+    /// assert!(iban.separator_positions().eq([4, 9, 14, 19, 24]));
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    pub fn separator_positions(&self) -> impl Iterator<Item = usize> {
+        (1..self.group_count()).map(|group| group * 5 - 1)
+    }
+
+    /// Parse a string as an Iban.
+    ///
+    /// This method attempts to parse a string as an `Iban`. It returns a `Result`
+    /// containing the parsed `Iban` if successful, or a [`ParseError`] if the string
+    /// could not be parsed as an `Iban`.
+    ///
+    /// ASCII whitespace is discarded. Letters are converted to uppercase, except in BBAN
+    /// segments that allow both upper and lower case (`c` in the IBAN registry), which keep
+    /// their case. Banks in countries like Qatar (QA) or Bahrain (BH) may treat these segments
+    /// as case-sensitive.
+    ///
+    /// Every country in the IBAN registry is supported, including those whose country code is
+    /// not an official ISO 3166-1 code, such as Kosovo (XK). See [`is_iso3166`].
+    ///
+    /// ```
+    /// let iban = iban::Iban::parse("qa58 dohb 0000 1234 5678 90ab cdef g")?;
+    /// assert_eq!(iban.as_str(), "QA58DOHB00001234567890abcdefg");
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns a `ParseError` for any of the following issues. If the input has
+    /// several issues, the first one in this list is returned:
+    /// * Country code format issues (see: `ParseError::CountryCode`)
+    /// * Check digit format issues (see: `ParseError::CheckDigit`)
+    /// * Unknown country (see: `ParseError::UnknownCountry`)
+    /// * Unsupported country format (see: `ParseError::UnsupportedFormat`)
+    /// * Invalid characters (see: `ParseError::InvalidCharacter`)
+    /// * Too short for the country (see: `ParseError::TooShort`)
+    /// * Too long for the country (see: `ParseError::TooLong`)
+    /// * Invalid BBAN format (see: `ParseError::InvalidBban`), reporting the first invalid position
+    /// * Checksum is wrong (see: `ParseError::WrongChecksum`)
+    ///
+    /// The one exception is that parsing stops at the first character beyond the country's
+    /// expected length, so invalid characters after it are reported as `ParseError::TooLong`.
+    #[inline]
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        FromStr::from_str(s)
+    }
+
+    /// Parse a string as an Iban, tolerating invisible formatting characters.
+    ///
+    /// This behaves like [`Iban::parse`], but before validation it also discards:
+    /// * Unicode whitespace (not only ASCII whitespace)
+    /// * Unicode default-ignorable code points, such as zero-width spaces and joiners
+    /// * Bidirectional control characters, such as the left-to-right (U+200E) and
+    ///   right-to-left (U+200F) marks
+    /// * The byte order mark (U+FEFF)
+    /// * Trailing control characters, such as NUL (U+0000)
+    /// * Double quotes around the IBAN, and a single leading apostrophe (`'`)
+    ///
+    /// These commonly end up in IBANs copied from web pages and right-to-left contexts,
+    /// or exported from Windows tools and spreadsheets. Spreadsheets quote cells in CSV
+    /// exports, and a leading apostrophe forces a cell to be formatted as text.
+    ///
+    /// Full-width forms of ASCII letters and digits (such as `Ａ` (U+FF21) and `０` (U+FF10)),
+    /// as typed with some East Asian input methods, are replaced by the ASCII characters.
+    ///
+    /// The remaining characters are expected to be in logical order, which is what discarding
+    /// the bidirectional controls of a right-to-left context leaves. They are never reordered,
+    /// so an IBAN that was stored in visual order (for example with its groups reversed) is
+    /// rejected.
+    ///
+    /// ```
+    /// // An IBAN embedded in Arabic text, isolated as left-to-right.
+    /// let iban = iban::Iban::parse_tolerant("\u{2066}SA03 8000 0000 6080 1016 7519\u{2069}")?;
+    /// assert_eq!(iban.as_str(), "SA0380000000608010167519");
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`].
+    pub fn parse_tolerant(s: &str) -> Result<Self, ParseError> {
+        Self::parse_bytes(tolerant_bytes(s), Case::Preserve)
+    }
+
+    /// Parse a string as an Iban, with the given [`Strictness`].
+    ///
+    /// ```
+    /// use iban::{Iban, ParseError, Strictness};
+    ///
+    /// let input = "IBAN: de89 3704 0044 0532 0130 00";
+    /// assert!(Iban::parse_level(input, Strictness::Lenient).is_ok());
+    /// assert_eq!(Iban::parse_level(input, Strictness::Standard), Err(ParseError::CheckDigit));
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`].
+    pub fn parse_level(s: &str, strictness: Strictness) -> Result<Self, ParseError> {
+        match strictness {
+            Strictness::Lenient => Self::parse_tolerant(strip_iban_prefix(s)),
+            Strictness::Standard => Self::parse(s),
+            Strictness::Strict => Self::parse_strict(s),
+        }
+    }
+
+    /// Parse a string as an Iban, replacing an aliased country code with its canonical code.
+    ///
+    /// `aliases` is a table of `(alias, canonical)` country code pairs, which is searched
+    /// case-insensitively. If the IBAN's country code is an alias, it is replaced by the
+    /// canonical code before the IBAN is parsed as with [`Iban::parse`], and the returned
+    /// `Iban` has the canonical code.
+    ///
+    /// The check digits are validated against the canonical code, so aliasing only helps when
+    /// the data uses the alias consistently, as a label for IBANs that were issued with the
+    /// canonical code.
+    ///
+    /// ```
+    /// let aliases = [("XD", "DE")];
+    /// let iban = iban::Iban::parse_with_aliases("XD89 3704 0044 0532 0130 00", &aliases)?;
+    /// assert_eq!(iban.as_str(), "DE89370400440532013000");
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`], for the IBAN with the canonical
+    /// country code.
+    pub fn parse_with_aliases(s: &str, aliases: &[(&str, &str)]) -> Result<Self, ParseError> {
+        let mut characters = s.bytes().filter(|byte| !byte.is_ascii_whitespace());
+        let country_code = [characters.next(), characters.next()];
+
+        let canonical = match country_code {
+            [Some(first), Some(second)] => aliases
+                .iter()
+                .find(|(alias, _)| alias.as_bytes().eq_ignore_ascii_case(&[first, second]))
+                .map(|&(_, canonical)| canonical),
+            _ => None,
+        };
+
+        match canonical {
+            Some(canonical) => {
+                Self::parse_bytes(canonical.bytes().chain(characters), Case::Preserve)
+            }
+            None => Self::parse_bytes(
+                country_code.into_iter().flatten().chain(characters),
+                Case::Preserve,
+            ),
+        }
+    }
+
+    /// Parse a string as an Iban, reporting a hint about the likely cause of any error.
+    ///
+    /// The input is parsed as with [`Iban::parse_tolerant`]. If that fails, a few common
+    /// data-entry mistakes are checked for, and reported as a [`Hint`] alongside the error:
+    /// * The input is empty ([`Hint::Empty`])
+    /// * The country code and check digits are swapped ([`Hint::SwappedCountryAndCheckDigits`])
+    /// * The country code is typed twice ([`Hint::DuplicatedCountryCode`])
+    /// * The IBAN is too short or too long for its country ([`Hint::WrongLength`])
+    ///
+    /// ```
+    /// use iban::{Hint, Iban, ParseError};
+    ///
+    /// let diagnostic = Iban::parse_verbose("89DE 3704 0044 0532 0130 00").unwrap_err();
+    ///
+    /// assert_eq!(diagnostic.error, ParseError::CountryCode);
+    /// assert_eq!(
+    ///     diagnostic.hint,
+    ///     Some(Hint::SwappedCountryAndCheckDigits("DE89370400440532013000".parse()?))
+    /// );
+    /// # Ok::<(), ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`], wrapped in a [`Diagnostic`].
+    pub fn parse_verbose(s: &str) -> Result<Self, Diagnostic> {
+        Self::parse_tolerant(s).map_err(|error| Diagnostic {
+            error,
+            hint: Self::hint(s, error),
+        })
+    }
+
+    /// Parse a string as an Iban, reporting details about its country and the input.
+    ///
+    /// The input is parsed as with [`Iban::parse`], and the result is returned along with the
+    /// details that are typically logged for a payment.
+    ///
+    /// ```
+    /// let parsed = iban::Iban::parse_detailed("de89 3704 0044 0532 0130 00")?;
+    /// assert_eq!(parsed.iban.as_str(), "DE89370400440532013000");
+    /// assert_eq!(parsed.country_name, "Germany");
+    /// assert!(parsed.sepa);
+    /// assert!(parsed.normalized);
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`].
+    pub fn parse_detailed(s: &str) -> Result<ParsedIban, ParseError> {
+        let (iban, normalized) = parse_and_report(s)?;
+        let country = iban.country_info();
+
+        Ok(ParsedIban {
+            iban,
+            country_name: country.name,
+            sepa: iban.is_sepa(),
+            normalized,
+        })
+    }
+
+    /// Determines a hint for an input that failed to parse with `error`.
+    fn hint(s: &str, error: ParseError) -> Option<Hint> {
+        if tolerant_bytes(s).next().is_none() {
+            return Some(Hint::Empty);
+        }
+
+        if let ParseError::TooShort { expected } | ParseError::TooLong { expected } = error {
+            // The country code was already validated before the length was checked.
+            let mut country_code = [0; 2];
+            for (byte, ch) in country_code.iter_mut().zip(tolerant_bytes(s)) {
+                *byte = ch.to_ascii_uppercase();
+            }
+            let (&country_code, _) =
+                COUNTRIES.get_entry(core::str::from_utf8(&country_code).ok()?)?;
+
+            return Some(Hint::WrongLength {
+                country_code,
+                expected,
+                length: tolerant_bytes(s).count(),
+            });
+        }
+
+        let mut characters = arrayvec::ArrayVec::<u8, IBAN_MAX_LENGTH>::new();
+        for byte in tolerant_bytes(s) {
+            characters.try_push(byte).ok()?;
+        }
+
+        if characters.len() > 4 && characters[..2].eq_ignore_ascii_case(&characters[2..4]) {
+            if let Ok(iban) = Self::parse_bytes(characters[2..].iter().copied(), Case::Preserve) {
+                return Some(Hint::DuplicatedCountryCode(iban));
+            }
+        }
+
+        let swapped = characters.get(2..4)?.iter().chain(&characters[..2]);
+        let swapped = swapped.chain(&characters[4..]).copied();
+        if let Ok(iban) = Self::parse_bytes(swapped, Case::Preserve) {
+            return Some(Hint::SwappedCountryAndCheckDigits(iban));
+        }
+
+        None
+    }
+
+    /// Parse the account line of a SWIFT MT message field as an Iban.
+    ///
+    /// Account lines in fields such as 57a and 59 of an MT103 prefix the account with
+    /// a `/`, and are followed by lines containing the name and address. Before the
+    /// IBAN is validated as with [`Iban::parse`], this method:
+    /// * Discards everything after the first line
+    /// * Removes surrounding whitespace
+    /// * Removes leading and trailing `/` characters
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`].
+    pub fn parse_field(s: &str) -> Result<Self, ParseError> {
+        let line = s.lines().next().unwrap_or_default();
+        Self::parse(line.trim().trim_matches('/'))
+    }
+
+    /// Format the IBAN as the account line of a SWIFT MT message field.
+    ///
+    /// The account line of fields such as 57a and 59 of an MT103 consists of a `/`
+    /// followed by the IBAN in electronic format, without any spaces. This is the
+    /// inverse of [`Iban::parse_field`].
+    ///
+    /// ```
+    /// let iban: iban::Iban = "DE89 3704 0044 0532 0130 00".parse()?;
+    /// assert_eq!(iban.to_mt_account(), "/DE89370400440532013000");
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_mt_account(&self) -> alloc::string::String {
+        let mut account = alloc::string::String::with_capacity(self.len() + 1);
+        account.push('/');
+        account.push_str(self);
+        account
+    }
+
+    /// Get the electronic format of the IBAN as a shared string.
+    ///
+    /// This is useful for caches and structures that hold many references to a few IBANs,
+    /// as clones of the returned `Arc` share a single allocation.
+    ///
+    /// ```
+    /// let iban: iban::Iban = "DE89 3704 0044 0532 0130 00".parse()?;
+    /// assert_eq!(&*iban.to_shared(), "DE89370400440532013000");
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_shared(&self) -> alloc::sync::Arc<str> {
+        alloc::sync::Arc::from(self.as_str())
+    }
+
+    /// Parse a string as an Iban, only accepting the electronic format of ISO 13616.
+    ///
+    /// Unlike [`Iban::parse`], which is lenient about spacing and case, this rejects:
+    /// * Whitespace, with `ParseError::InvalidCharacter`
+    /// * A lowercase country code, with `ParseError::CountryCode`
+    /// * Lowercase characters in BBAN segments that only allow upper case, with
+    ///   `ParseError::InvalidBban`
+    ///
+    /// Characters in BBAN segments that allow both upper and lower case (`c` in the IBAN
+    /// registry) are accepted in either case, and keep their case as with [`Iban::parse`].
+    ///
+    /// ```
+    /// use iban::{Iban, ParseError};
+    ///
+    /// assert!(Iban::parse_strict("DE89370400440532013000").is_ok());
+    /// assert_eq!(Iban::parse_strict("de89370400440532013000"), Err(ParseError::CountryCode));
+    /// assert!(Iban::parse("de89370400440532013000").is_ok());
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`].
+    pub fn parse_strict(s: &str) -> Result<Self, ParseError> {
+        Self::parse_bytes(s.bytes(), Case::Strict)
+    }
+
+    /// Parse a string as an Iban, converting all characters to uppercase.
+    ///
+    /// Unlike [`Iban::parse`], this also uppercases characters in BBAN segments that allow both
+    /// upper and lower case (`c` in the IBAN registry), as earlier versions of this crate did.
+    /// This is useful for comparing IBANs with systems that store them in upper case, but loses
+    /// the case of accounts where it is significant.
+    ///
+    /// ```
+    /// use iban::Iban;
+    ///
+    /// let iban = Iban::parse_uppercase("QA58 DOHB 0000 1234 5678 90Ab cDef G")?;
+    /// assert_eq!(iban.as_str(), "QA58DOHB00001234567890ABCDEFG");
+    ///
+    /// let iban = Iban::parse("QA58 DOHB 0000 1234 5678 90Ab cDef G")?;
+    /// assert_eq!(iban.as_str(), "QA58DOHB00001234567890AbcDefG");
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`].
+    pub fn parse_uppercase(s: &str) -> Result<Self, ParseError> {
+        Self::parse_bytes(
+            s.bytes().filter(|byte| !byte.is_ascii_whitespace()),
+            Case::Upper,
+        )
+    }
+
+    /// Parse a string as an Iban, using `scratch` as the working buffer.
+    ///
+    /// This behaves like [`Iban::parse`], but validates the IBAN in the caller-provided buffer,
+    /// which can be reused across many parses. On success, `scratch` contains the electronic
+    /// format of the returned `Iban`. On failure, its contents are unspecified.
+    ///
+    /// Note that creating an empty `ArrayString` is free, as its storage is not initialized,
+    /// so this is not measurably faster than [`Iban::parse`]. It is provided for workflows that
+    /// manage their buffers explicitly.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    /// use iban::Iban;
+    ///
+    /// let mut scratch = ArrayString::new();
+    /// for input in ["DE89 3704 0044 0532 0130 00", "GB82 WEST 1234 5698 7654 32"] {
+    ///     let iban = Iban::parse_into(input, &mut scratch)?;
+    ///     assert_eq!(iban, scratch);
+    /// }
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`].
+    pub fn parse_into(
+        s: &str,
+        scratch: &mut ArrayString<IBAN_MAX_LENGTH>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_structure_into(
+            s.bytes().filter(|byte| !byte.is_ascii_whitespace()),
+            Case::Preserve,
+            scratch,
+        )?;
+
+        if calculate_checksum(scratch.as_bytes()) != 1 {
+            return Err(ParseError::WrongChecksum);
+        }
+
+        Ok(Self(*scratch))
+    }
+
+    /// Parse a string as an Iban, appending its electronic format to `out`.
+    ///
+    /// This behaves like [`Iban::parse`], and on success the validated electronic format of the
+    /// returned `Iban` is appended to `out`. This allows many IBANs to be collected into one
+    /// contiguous buffer. On failure, `out` is left unchanged.
+    ///
+    /// ```
+    /// let mut out = Vec::new();
+    /// iban::Iban::parse_into_vec("de89 3704 0044 0532 0130 00", &mut out)?;
+    /// assert_eq!(out, b"DE89370400440532013000");
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`].
+    #[cfg(feature = "alloc")]
+    pub fn parse_into_vec(s: &str, out: &mut alloc::vec::Vec<u8>) -> Result<Self, ParseError> {
+        let iban = Self::parse(s)?;
+        out.extend_from_slice(iban.as_bytes());
+        Ok(iban)
+    }
+
+    /// Parse a string as an Iban, replacing its check digits with the correct ones.
+    ///
+    /// The input is validated as with [`Iban::parse`], except for the checksum. The check
+    /// digits are then recalculated, so the returned `Iban` is always valid. This is useful
+    /// for cleaning up data where the check digits are known to be wrong.
+    ///
+    /// Note that the check digits are the only protection against typos in the BBAN,
+    /// so a repaired IBAN is not guaranteed to identify the intended account.
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`], except for `ParseError::WrongChecksum`.
+    pub fn repair_check_digits(s: &str) -> Result<Self, ParseError> {
+        let structure = Self::parse_structure(
+            s.bytes().filter(|byte| !byte.is_ascii_whitespace()),
+            Case::Preserve,
+        )?;
+        let (country_code, bban) = (&structure[..2], &structure[4..]);
+
+        let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
+        iban.push_str(country_code);
+        for digit in check_digits(country_code, bban) {
+            iban.push(char::from(digit));
+        }
+        iban.push_str(bban);
+
+        Ok(Self(iban))
+    }
+
+    /// Create an IBAN for a different account at the same bank and branch.
+    ///
+    /// The country code, bank identifier and branch identifier are kept, and the rest of the
+    /// BBAN (usually the account number, along with any national check digits) is replaced by
+    /// `account`. The check digits are then recalculated, so the returned `Iban` is always valid.
+    ///
+    /// Note that national check digits are not recalculated, so for countries that have them,
+    /// `account` must include them.
+    ///
+    /// ```
+    /// let iban: iban::Iban = "DE89370400440532013000".parse()?;
+    /// let other = iban.with_account("1234567890")?;
+    /// assert_eq!(other.as_str(), "DE14370400441234567890");
+    /// assert_eq!(other.bban().bank_identifier(), iban.bban().bank_identifier());
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `ParseError` if `account` does not match the format of the rest of the BBAN:
+    /// * An account that is too short, or has an invalid character, is reported as
+    ///   `ParseError::InvalidBban` at the first missing or invalid position
+    /// * An account that is too long is reported as `ParseError::TooLong`
+    pub fn with_account(&self, account: &str) -> Result<Self, ParseError> {
+        let country = self.country_info();
+        let format = country_format(country)?;
+
+        let mut bban = arrayvec::ArrayVec::<u8, IBAN_MAX_LENGTH>::new();
+        bban.extend(self.bban().bytes());
+        let mut account = account.bytes();
+        for (position, expected) in bban_character_types(format).enumerate() {
+            if is_identifier(country, position) {
+                continue;
+            }
+            bban[position] = account.next().ok_or(ParseError::InvalidBban {
+                position: position + 4,
+                expected,
+            })?;
+        }
+        if account.next().is_some() {
+            return Err(ParseError::TooLong {
+                expected: country.length,
+            });
+        }
+
+        let structure = Self::parse_structure(
+            self.country_code()
+                .bytes()
+                .chain(*b"00")
+                .chain(bban.iter().copied()),
+            Case::Preserve,
+        )?;
+        let (country_code, bban) = (&structure[..2], &structure[4..]);
+
+        let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
+        iban.push_str(country_code);
+        for digit in check_digits(country_code, bban) {
+            iban.push(char::from(digit));
+        }
+        iban.push_str(bban);
+
+        Ok(Self(iban))
+    }
+
+    /// Create an IBAN for the next account at the same bank and branch.
+    ///
+    /// The rest of the BBAN after removing the bank and branch identifiers (usually the account
+    /// number) is treated as a decimal number and incremented by one, as with
+    /// [`Iban::with_account`]. The check digits are then recalculated.
+    ///
+    /// Returns `None` if the rest of the BBAN is not numeric in the country's format, or if it
+    /// consists only of nines, as the account would overflow. Note that national check digits
+    /// that are part of the account are incremented as well, rather than recalculated.
+    ///
+    /// ```
+    /// let iban: iban::Iban = "DE89370400440532013000".parse()?;
+    /// let next = iban.increment_account().unwrap();
+    /// assert_eq!(next.as_str(), "DE62370400440532013001");
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    #[must_use]
+    pub fn increment_account(&self) -> Option<Self> {
+        let country = self.country_info();
+        let format = country_format(country).ok()?;
+
+        let mut account = arrayvec::ArrayVec::<u8, IBAN_MAX_LENGTH>::new();
+        for ((position, character_type), ch) in bban_character_types(format)
+            .enumerate()
+            .zip(self.bban().bytes())
+        {
+            if is_identifier(country, position) {
+                continue;
+            }
+            if character_type != CharacterType::N {
+                return None;
+            }
+            account.push(ch);
+        }
+
+        for digit in account.iter_mut().rev() {
+            if *digit == b'9' {
+                *digit = b'0';
+            } else {
+                *digit += 1;
+                let account = core::str::from_utf8(&account).ok()?;
+                return self.with_account(account).ok();
+            }
+        }
+
+        None
+    }
+
+    /// Parse a string as an Iban, without validating its checksum.
+    ///
+    /// The input is validated as with [`Iban::parse`], except for the checksum, so the returned
+    /// `Iban` is structurally valid: it has a known country, the expected length, and a BBAN that
+    /// matches the country's format. Accessors that rely on that structure, such as
+    /// [`Bban::bank_identifier`] and [`Bban::branch_identifier`], are meaningful even if the
+    /// check digits are wrong.
+    ///
+    /// This is intended for previewing the bank and branch of an IBAN while it is being entered.
+    /// As the check digits are the only protection against typos, the result should not be
+    /// used for payments. Use [`Iban::parse`] to check that the IBAN is actually valid.
+    ///
+    /// ```
+    /// use iban::{Iban, ParseError};
+    ///
+    /// let iban = Iban::parse_unchecked("DE00 3704 0044 0532 0130 00")?;
+    /// assert_eq!(iban.bban().bank_identifier(), Some("37040044"));
+    /// assert_eq!(Iban::parse(&iban), Err(ParseError::WrongChecksum));
+    /// # Ok::<(), ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`], except for `ParseError::WrongChecksum`.
+    pub fn parse_unchecked(s: &str) -> Result<Self, ParseError> {
+        Self::parse_structure(
+            s.bytes().filter(|byte| !byte.is_ascii_whitespace()),
+            Case::Preserve,
+        )
+        .map(Self)
+    }
+
+    /// Parse a string as an Iban, validating its checksum with the given algorithm.
+    ///
+    /// The input is validated as with [`Iban::parse`], except that the checksum is validated by
+    /// calling `checksum` with the electronic format of the IBAN, instead of checking that its
+    /// ISO 7064 MOD 97-10 checksum is 1. This allows identifiers that reuse the structure of IBANs
+    /// with a different check algorithm to be parsed. The check digits must still be two digits.
+    ///
+    /// The result is only interoperable with other IBAN implementations if the standard
+    /// algorithm is used, as done by [`Iban::parse`].
+    ///
+    /// ```
+    /// let checksum = |iban: &[u8]| &iban[2..4] == b"00";
+    /// let iban = iban::Iban::parse_with_checksum("DE00 3704 0044 0532 0130 00", &checksum)?;
+    /// assert_eq!(iban.bban().bank_identifier(), Some("37040044"));
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`], where `ParseError::WrongChecksum`
+    /// is returned if `checksum` returns `false`.
+    pub fn parse_with_checksum(
+        s: &str,
+        checksum: &impl Fn(&[u8]) -> bool,
+    ) -> Result<Self, ParseError> {
+        let iban = Self::parse_structure(
+            s.bytes().filter(|byte| !byte.is_ascii_whitespace()),
+            Case::Preserve,
+        )?;
+
+        if !checksum(iban.as_bytes()) {
+            return Err(ParseError::WrongChecksum);
+        }
+
+        Ok(Self(iban))
+    }
+
+    /// Parse a string as an Iban, also accepting a non-standard MOD 97-10 remainder.
+    ///
+    /// This behaves like [`Iban::parse`], except that the checksum is valid if the remainder
+    /// calculated by [`calculate_checksum`] is either 1 (as required by ISO 13616) or `remainder`.
+    ///
+    /// This is a compatibility shim for legacy systems that calculate check digits incorrectly,
+    /// for example ones that produce a remainder of 0. IBANs accepted only because of `remainder`
+    /// are not valid IBANs, and will be rejected by other systems and by [`Iban::parse`].
+    ///
+    /// ```
+    /// use iban::{Iban, ParseError};
+    ///
+    /// let legacy = "DE88 3704 0044 0532 0130 00";
+    /// assert!(Iban::parse_with_remainder(legacy, 0).is_ok());
+    /// assert_eq!(Iban::parse(legacy), Err(ParseError::WrongChecksum));
+    /// ```
+    ///
+    /// # Errors
+    /// This method returns the same errors as [`Iban::parse`].
+    pub fn parse_with_remainder(s: &str, remainder: u32) -> Result<Self, ParseError> {
+        Self::parse_with_checksum(s, &|iban| {
+            let checksum = calculate_checksum(iban);
+            checksum == 1 || checksum == remainder
+        })
+    }
+
+    /// Parses an IBAN from already filtered characters.
+    ///
+    /// This is the shared implementation of [`Iban::from_str`] and the other parsing
+    /// methods, which only differ in which characters they discard before validation.
+    ///
+    /// The handling of lowercase characters is determined by `case`, see [`Case`].
+    fn parse_bytes<I: Iterator<Item = u8>>(characters: I, case: Case) -> Result<Self, ParseError> {
+        let iban = Self::parse_structure(characters, case)?;
+
+        if calculate_checksum(iban.as_bytes()) != 1 {
+            return Err(ParseError::WrongChecksum);
+        }
+
+        Ok(Self(iban))
+    }
+
+    /// Validates the country code and check digits of an IBAN from already filtered characters.
+    ///
+    /// The buffer `iban` is cleared, and then filled with only the country code and check digits.
+    /// The country code is uppercased, unless `case` is [`Case::Strict`].
+    fn parse_prefix<I: Iterator<Item = u8>>(
+        characters: &mut I,
+        case: Case,
+        iban: &mut ArrayString<IBAN_MAX_LENGTH>,
+    ) -> Result<(), ParseError> {
+        iban.clear();
+
+        for _ in 0..2 {
+            let ch = characters
+                .next()
+                .map(|ch| match case {
+                    Case::Strict => ch,
+                    Case::Preserve | Case::Upper => ch.to_ascii_uppercase(),
+                })
+                .filter(u8::is_ascii_uppercase)
+                .ok_or(ParseError::CountryCode)?;
+            iban.push(char::from(ch));
+        }
+
+        for _ in 0..2 {
+            let ch = characters
+                .next()
+                .filter(u8::is_ascii_digit)
+                .ok_or(ParseError::CheckDigit)?;
+            iban.push(char::from(ch));
+        }
+
+        Ok(())
+    }
+
+    /// Validates the structure of an IBAN from already filtered characters, without verifying the checksum.
+    ///
+    /// See [`Iban::parse_bytes`] for details.
+    fn parse_structure<I: Iterator<Item = u8>>(
+        characters: I,
+        case: Case,
+    ) -> Result<ArrayString<IBAN_MAX_LENGTH>, ParseError> {
+        let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
+        Self::parse_structure_into(characters, case, &mut iban)?;
+        Ok(iban)
+    }
+
+    /// Validates the structure of an IBAN into the buffer `iban`, see [`Iban::parse_structure`].
+    ///
+    /// The buffer is cleared first, and its contents are unspecified if an error is returned.
+    fn parse_structure_into<I: Iterator<Item = u8>>(
+        mut characters: I,
+        case: Case,
+        iban: &mut ArrayString<IBAN_MAX_LENGTH>,
+    ) -> Result<(), ParseError> {
+        Self::parse_prefix(&mut characters, case, iban)?;
+
+        let country_code = &iban[..2];
+        let country = COUNTRIES
+            .get(country_code)
+            .ok_or(ParseError::UnknownCountry)?;
+        let (expected_length, validation) = (country.length, country_format(country)?);
+
+        let mut validation = bban_character_types(validation);
+
+        // BBAN format errors are only reported once the length is known to be right, so that the
+        // error precedence does not depend on where the first invalid character is.
+        let mut bban_error = None;
+
+        for (position, ch) in (iban.len()..).zip(characters) {
+            if !ch.is_ascii_alphanumeric() {
+                return Err(ParseError::InvalidCharacter);
+            }
+
+            // Validation must have at least as many characters as input. This bails on the first
+            // character beyond the country's expected length, so the work done is bounded by it
+            // rather than by the length of the (possibly untrusted) input.
+            let character_type = validation.next().ok_or(ParseError::TooLong {
+                expected: expected_length,
+            })?;
+            if bban_error.is_some() {
+                continue;
+            }
+
+            let ch = case.apply(character_type, ch);
+            if !character_type.contains(ch) {
+                bban_error = Some(ParseError::InvalidBban {
+                    position,
+                    expected: character_type,
+                });
+                continue;
+            }
+
+            iban.try_push(char::from(ch))
+                .map_err(|_| ParseError::TooLong {
+                    expected: expected_length,
+                })?;
+        }
+
+        // Input must have at least as many characters as validation.
+        if validation.next().is_some() {
+            return Err(ParseError::TooShort {
+                expected: expected_length,
+            });
+        }
+
+        if let Some(error) = bban_error {
+            return Err(error);
+        }
+
+        // Since we check validation.len() >= input.len() and input.len() >= validation.len(),
+        // we know that they are equal and this should be impossible.
+        debug_assert_eq!(expected_length, iban.len());
+
+        Ok(())
+    }
+
+    /// Consume the IBAN, returning the underlying buffer.
+    ///
+    /// The buffer contains the electronic-format representation of the IBAN.
+    #[inline]
+    #[must_use]
+    pub const fn into_inner(self) -> ArrayString<IBAN_MAX_LENGTH> {
+        self.0
+    }
+
+    /// Construct an `Iban` from a buffer without performing any validation.
+    ///
+    /// This is intended for callers that have already validated the IBAN elsewhere
+    /// (for example, a buffer previously obtained from [`Iban::into_inner`]) and want
+    /// to avoid re-running the validation and checksum calculation.
+    ///
+    /// # Safety
+    /// The caller must ensure that `inner` contains a valid IBAN in electronic format,
+    /// exactly as [`Iban::parse`] would have produced it. Other methods (such as
+    /// [`Bban::bank_identifier`]) slice the buffer based on the country code, and may
+    /// panic or return nonsensical results if this invariant is not upheld.
+    ///
+    /// In debug builds, this asserts that `inner` is well-formed (see [`Iban::is_well_formed`]),
+    /// to catch buffers that were mangled after validation. The checksum is not verified.
+    #[inline]
+    #[must_use]
+    pub unsafe fn from_inner_unchecked(inner: ArrayString<IBAN_MAX_LENGTH>) -> Self {
+        debug_assert!(
+            Self::is_well_formed(&inner),
+            "buffer is not an IBAN in electronic format: {inner:?}"
+        );
+        Self(inner)
+    }
+
+    /// Checks the invariants of an `Iban` buffer, for debug assertions on unchecked constructors.
+    ///
+    /// The buffer must consist of an upper case country code, two check digits, and upper case
+    /// ASCII letters and digits. Lowercase letters are only allowed in BBAN segments that allow
+    /// them (`c` in the IBAN registry), as kept by [`Iban::parse`].
+    fn is_well_formed(inner: &str) -> bool {
+        let bytes = inner.as_bytes();
+        if bytes.len() < 4
+            || !bytes[..2].iter().all(u8::is_ascii_uppercase)
+            || !bytes[2..4].iter().all(u8::is_ascii_digit)
+        {
+            return false;
+        }
+
+        let format = COUNTRIES
+            .get(&inner[..2])
+            .and_then(|country| country_format(country).ok());
+        bytes[4..].iter().enumerate().all(|(position, ch)| {
+            ch.is_ascii_uppercase()
+                || ch.is_ascii_digit()
+                || (ch.is_ascii_lowercase()
+                    && format.map_or(false, |format| {
+                        bban_character_types(format).nth(position) == Some(CharacterType::C)
+                    }))
+        })
+    }
+
+    /// Generates a random IBAN for the specified `country_code` using the given `rng`.
+    ///
+    /// # Returns
+    /// If successful, returns an `Iban` instance representing the generated IBAN.
+    ///
+    /// # Errors
+    /// Returns a `ParseError` if the specified `country_code` is invalid or unknown.
+    #[cfg(feature = "rand")]
+    pub fn rand<R: ?Sized + rand::Rng>(
+        country_code: &str,
+        rng: &mut R,
+    ) -> Result<Self, ParseError> {
+        let mut code = [0; 2];
+        let mut country_code = country_code.as_bytes().iter().map(u8::to_ascii_uppercase);
+
+        for byte in &mut code {
+            *byte = country_code
+                .next()
+                .filter(u8::is_ascii_uppercase)
+                .ok_or(ParseError::CountryCode)?;
+        }
+
+        if country_code.next().is_some() {
+            return Err(ParseError::UnknownCountry);
+        }
+
+        let country_code = core::str::from_utf8(&code).map_err(|_| ParseError::CountryCode)?;
+        let country = COUNTRIES
+            .get(country_code)
+            .ok_or(ParseError::UnknownCountry)?;
+        let (expected_length, validation) = (country.length, country_format(country)?);
+
+        // The BBAN is generated first, so that the IBAN can be assembled in order once the
+        // check digits are known.
+        let mut bban = ArrayString::<IBAN_MAX_LENGTH>::new();
+        let bban_chars = bban_character_types(validation)
+            .map(|character_type| char::from(character_type.rand(rng)));
+
+        for character in bban_chars {
+            bban.try_push(character).map_err(|_| ParseError::TooLong {
+                expected: expected_length,
+            })?;
+        }
+
+        let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
+        iban.push_str(country_code);
+        for digit in check_digits(country_code, &bban) {
+            iban.push(char::from(digit));
+        }
+        iban.try_push_str(&bban).map_err(|_| ParseError::TooLong {
+            expected: expected_length,
+        })?;
+
+        debug_assert_eq!(iban.len(), expected_length);
+
+        Ok(Self(iban))
+    }
+
+    /// Generates a random IBAN with the given bank and branch identifiers, using the given `rng`.
+    ///
+    /// The identifiers are placed at their offsets in the BBAN, and only the rest of the BBAN
+    /// (usually the account number) is random. If `branch_id` is `None`, the branch identifier
+    /// (if the country has one) is random as well. Letters in the identifiers may be in either
+    /// case. The check digits are then calculated, so the IBAN is always valid.
+    ///
+    /// ```
+    /// # use rand::SeedableRng;
+    /// # let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    /// let iban = iban::Iban::rand_account("GB", "WEST", Some("123456"), &mut rng)?;
+    /// assert_eq!(iban.bban().bank_identifier(), Some("WEST"));
+    /// assert_eq!(iban.bban().branch_identifier(), Some("123456"));
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `ParseError` if:
+    /// * The specified `country_code` is invalid or unknown, as with [`Iban::rand`]
+    /// * The country does not have a bank identifier, or a `branch_id` is given but the country
+    ///   does not have a branch identifier (see: `ParseError::UnsupportedFormat`)
+    /// * An identifier does not match the format of its segment (see: `ParseError::InvalidBban`
+    ///   and `ParseError::InvalidCharacter`). An identifier of the wrong length is reported at
+    ///   the position of its first missing or extra character.
+    #[cfg(feature = "rand")]
+    pub fn rand_account<R: ?Sized + rand::Rng>(
+        country_code: &str,
+        bank_id: &str,
+        branch_id: Option<&str>,
+        rng: &mut R,
+    ) -> Result<Self, ParseError> {
+        let random = Self::rand(country_code, rng)?;
+        let country = random.country_info();
+        let mut bban = arrayvec::ArrayVec::<u8, IBAN_MAX_LENGTH>::new();
+        bban.extend(random.bban().bytes());
+
+        let segments = [
+            (country.bank_identifier, Some(bank_id)),
+            (country.branch_identifier, branch_id),
+        ];
+        for (offset, identifier) in segments {
+            let identifier = match identifier {
+                Some(identifier) => identifier,
+                None => continue,
+            };
+            let (start, end) = offset.ok_or(ParseError::UnsupportedFormat)?;
+
+            if identifier.len() != end - start {
+                let position = start + identifier.len().min(end - start);
+                return Err(bban_character_types(country.format).nth(position).map_or(
+                    ParseError::TooLong {
+                        expected: country.length,
+                    },
+                    |expected| ParseError::InvalidBban {
+                        position: position + 4,
+                        expected,
+                    },
+                ));
+            }
+            bban[start..end].copy_from_slice(identifier.as_bytes());
+        }
+
+        let structure = Self::parse_structure(
+            random
+                .country_code()
+                .bytes()
+                .chain(*b"00")
+                .chain(bban.iter().copied()),
+            Case::Preserve,
+        )?;
+        let (country_code, bban) = (&structure[..2], &structure[4..]);
+
+        let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
+        iban.push_str(country_code);
+        for digit in check_digits(country_code, bban) {
+            iban.push(char::from(digit));
+        }
+        iban.push_str(bban);
+
+        Ok(Self(iban))
+    }
+
+    /// Generates `count` distinct random IBANs for the specified `country_code` using the given `rng`.
+    ///
+    /// Each IBAN is generated as with [`Iban::rand`], and collisions are discarded
+    /// until `count` distinct IBANs have been generated. Generation gives up after 1000
+    /// consecutive collisions, such as when `count` exceeds the number of possible IBANs
+    /// for the country.
+    ///
+    /// # Errors
+    /// Returns a `ParseError` if the specified `country_code` is invalid or unknown, or
+    /// `ParseError::Exhausted` if generation gave up before `count` distinct IBANs were found.
+    #[cfg(all(feature = "rand", feature = "alloc"))]
+    pub fn rand_batch<R: ?Sized + rand::Rng>(
+        country_code: &str,
+        count: usize,
+        rng: &mut R,
+    ) -> Result<alloc::vec::Vec<Self>, ParseError> {
+        const MAX_ATTEMPTS: usize = 1000;
 
-        let bban_chars = validation
-            .iter()
-            .flat_map(|(count, character_type)| (0..*count).map(move |_| character_type))
-            .skip(4)
-            .map(|character_type| char::from(character_type.rand(rng)));
+        let mut seen = alloc::collections::BTreeSet::new();
+        let mut ibans = alloc::vec::Vec::with_capacity(count);
 
-        for character in bban_chars {
-            iban.try_push(character)
-                .map_err(|_| ParseError::InvalidLength)?;
+        let mut attempts = 0;
+        while ibans.len() < count {
+            let iban = Self::rand(country_code, rng)?;
+            if seen.insert(iban.0) {
+                ibans.push(iban);
+                attempts = 0;
+            } else {
+                attempts += 1;
+                if attempts == MAX_ATTEMPTS {
+                    return Err(ParseError::Exhausted);
+                }
+            }
         }
 
-        debug_assert_eq!(iban.len(), expected_length);
+        Ok(ibans)
+    }
 
-        let check_digits = 98 - calculate_checksum(iban.as_bytes());
-        #[allow(clippy::cast_possible_truncation)]
-        let check_digits = [
-            b'0' + (check_digits / 10) as u8,
-            b'0' + (check_digits % 10) as u8,
-        ];
+    /// Generates a random IBAN for the specified `country_code` that is likely to pass the
+    /// national check, using the given `rng`.
+    ///
+    /// This behaves like [`Iban::rand`], but retries generation until the national check digits
+    /// are valid (see [`Bban::validate_national_checksum`]). At most 1000 IBANs are generated,
+    /// after which the last one is returned even if it fails the national check. For the
+    /// currently supported national checks this happens with a probability of less than 0.01%.
+    ///
+    /// For countries without a supported national check, this is equivalent to [`Iban::rand`].
+    ///
+    /// # Errors
+    /// Returns a `ParseError` if the specified `country_code` is invalid or unknown.
+    #[cfg(feature = "rand")]
+    pub fn rand_plausible<R: ?Sized + rand::Rng>(
+        country_code: &str,
+        rng: &mut R,
+    ) -> Result<Self, ParseError> {
+        const MAX_ATTEMPTS: usize = 1000;
 
-        // TODO: Figure out a way to swap out the characters without unsafe.
-        // SAFETY: All of the characters generated are ASCII, so there are no issues with character boundries.
-        unsafe { &mut iban.as_bytes_mut()[2..4] }.copy_from_slice(&check_digits);
+        let mut iban = Self::rand(country_code, rng)?;
+        for _ in 1..MAX_ATTEMPTS {
+            if iban.bban().validate_national_checksum() != Some(false) {
+                break;
+            }
+            iban = Self::rand(country_code, rng)?;
+        }
 
-        Ok(Self(iban))
+        Ok(iban)
     }
 }
 
@@ -486,54 +1956,723 @@ impl Bban {
     #[inline]
     #[must_use]
     pub fn bank_identifier(&self) -> Option<&str> {
-        let (_expected_length, _validation, bank_offset, _branch_offset, _checksum_offset) =
-            COUNTRIES.get(self.country_code())?;
-        bank_offset
-            .as_ref()
+        let country = COUNTRIES.get(self.country_code())?;
+        country
+            .bank_identifier
+            .and_then(|(start, end)| self.get(start..end))
+    }
+
+    /// Get the bank identifier of the BBAN, guessing it from the format if the registry does not
+    /// define it.
+    ///
+    /// Returns the same value as [`Bban::bank_identifier`] if the country has a bank identifier.
+    /// Otherwise, the first segment of the country's BBAN format (such as `5!n` in `5!n5!n11!n2!n`)
+    /// is assumed to be the bank identifier.
+    ///
+    /// This is only a heuristic. The first segment may be shorter than the actual bank
+    /// identifier (for Benin (BJ), it is a single letter), and `None` is returned if the BBAN
+    /// consists of a single segment, as for Angola (AO) or Algeria (DZ).
+    ///
+    /// ```
+    /// let iban: iban::Iban = "BJ83A12312341234123412341234".parse()?;
+    /// assert_eq!(iban.bban().bank_identifier(), None);
+    /// assert_eq!(iban.bban().bank_identifier_heuristic(), Some("A"));
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    #[must_use]
+    pub fn bank_identifier_heuristic(&self) -> Option<&str> {
+        let country = COUNTRIES.get(self.country_code())?;
+        country
+            .bank_identifier
+            .or_else(|| first_bban_segment(country.format))
+            .and_then(|(start, end)| self.get(start..end))
+    }
+
+    /// Get the bank identifier of the BBAN without zero-padding (if it has one).
+    ///
+    /// Returns the same value as [`Bban::bank_identifier`], except that leading zeros are
+    /// removed for countries that zero-pad their numeric bank identifier. This is useful
+    /// for matching against bank directories that store the unpadded code.
+    ///
+    /// Leading zeros are only removed for Andorra (AD), Switzerland (CH), Cyprus (CY),
+    /// Greece (GR), Liechtenstein (LI) and Luxembourg (LU). A bank
+    /// identifier consisting only of zeros is trimmed to a single zero.
+    #[inline]
+    #[must_use]
+    pub fn bank_identifier_trimmed(&self) -> Option<&str> {
+        let bank_identifier = self.bank_identifier()?;
+        if !ZERO_PADDED_BANK_IDENTIFIERS.contains(&self.country_code())
+            || !bank_identifier.bytes().all(|byte| byte.is_ascii_digit())
+        {
+            return Some(bank_identifier);
+        }
+
+        match bank_identifier.trim_start_matches('0') {
+            "" => Some(&bank_identifier[bank_identifier.len() - 1..]),
+            trimmed => Some(trimmed),
+        }
+    }
+
+    /// Get the branch identifier of the BBAN (if it has one).
+    ///
+    /// Returns an `Option` containing a string slice representing the branch identifier,
+    /// or `None` if the BBAN does not have a branch identifier. The branch identifier's position
+    /// and length are determined by the country's IBAN specification.
+    #[inline]
+    #[must_use]
+    pub fn branch_identifier(&self) -> Option<&str> {
+        let country = COUNTRIES.get(self.country_code())?;
+        country
+            .branch_identifier
+            .and_then(|(start, end)| self.get(start..end))
+    }
+
+    /// Get the combined bank and branch identifier of the BBAN (if it has one).
+    ///
+    /// Returns an `Option` containing a string slice spanning both the bank identifier
+    /// and the branch identifier, or `None` if the BBAN does not have both of them,
+    /// or if the branch identifier does not immediately follow the bank identifier.
+    #[inline]
+    #[must_use]
+    pub fn clearing_identifier(&self) -> Option<&str> {
+        let country = COUNTRIES.get(self.country_code())?;
+        match (country.bank_identifier, country.branch_identifier) {
+            (Some((start, bank_end)), Some((branch_start, end))) if bank_end == branch_start => {
+                self.get(start..end)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the checksum of the BBAN (if it has one).
+    ///
+    /// Returns an `Option` containing a string slice representing the checksum,
+    /// or `None` if the BBAN does not have a checksum. The checksum's position
+    /// and length are determined by the country's IBAN specification.
+    #[inline]
+    #[must_use]
+    pub fn checksum(&self) -> Option<&str> {
+        let country = COUNTRIES.get(self.country_code())?;
+        country
+            .checksum
+            .and_then(|(start, end)| self.get(start..end))
+    }
+
+    /// Get the residual portion of the BBAN, not covered by any identified segment.
+    ///
+    /// Returns the largest contiguous span of the BBAN that is not part of the bank identifier,
+    /// branch identifier or checksum. This usually corresponds to the account number, for
+    /// countries where the registry does not define its position. If several spans are equally
+    /// large, the first one is returned. If the country defines none of these segments,
+    /// the whole BBAN is returned.
+    #[must_use]
+    pub fn residual(&self) -> &str {
+        let mut segments = COUNTRIES
+            .get(self.country_code())
+            .map_or([None; 3], |country| {
+                [
+                    country.bank_identifier,
+                    country.branch_identifier,
+                    country.checksum,
+                ]
+            });
+        // `None` sorts first, so identified segments are in order of their start offset.
+        segments.sort_unstable();
+
+        let mut residual = (0, 0);
+        let mut position = 0;
+        for (start, end) in segments
+            .iter()
+            .flatten()
             .copied()
-            .map(|(start, end)| &self[start..end])
+            .chain(core::iter::once((self.len(), self.len())))
+        {
+            if start > position && start - position > residual.1 - residual.0 {
+                residual = (position, start);
+            }
+            position = position.max(end);
+        }
+
+        &self[residual.0..residual.1]
+    }
+
+    /// Validate the national check digits of the BBAN (if the country has them).
+    ///
+    /// Returns `Some(true)` if the national check digits are valid, `Some(false)` if they
+    /// are not, or `None` if no national check algorithm is implemented for the country (or the
+    /// account is exempt from it, as for most Danish banks).
+    ///
+    /// A failed national check does not make the IBAN itself invalid, it only indicates
+    /// that the BBAN is unlikely to identify a real account.
+    #[inline]
+    #[must_use]
+    pub fn validate_national_checksum(&self) -> Option<bool> {
+        national::validate(self.country_code(), self)
+    }
+
+    /// Get the numeric value of the BBAN (if it is entirely numeric).
+    ///
+    /// Returns `None` if the BBAN contains any non-digit characters, or if
+    /// the value would not fit into a `u128`.
+    #[must_use]
+    pub fn as_u128(&self) -> Option<u128> {
+        self.bytes().try_fold(0u128, |value, byte| {
+            if !byte.is_ascii_digit() {
+                return None;
+            }
+
+            value.checked_mul(10)?.checked_add(u128::from(byte - b'0'))
+        })
+    }
+
+    /// Iterate over the characters of the BBAN, along with their expected [`CharacterType`].
+    ///
+    /// The character types are determined by the country's BBAN format, as defined in the IBAN registry.
+    pub fn chars_with_types(&self) -> impl Iterator<Item = (char, CharacterType)> + '_ {
+        let format = COUNTRIES
+            .get(self.country_code())
+            .map_or(&[][..], |country| country.format);
+        self.chars().zip(bban_character_types(format))
+    }
+
+    /// Get the BBAN as a string slice.
+    ///
+    /// Returns a reference to the underlying string (electronic-format) that represents the BBAN.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Get the BBAN as a byte slice.
+    ///
+    /// Returns the bytes of the electronic-format representation of the BBAN.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+
+    /// Get the IBAN that this BBAN belongs to.
+    ///
+    /// The returned `Iban` includes the country code and check digits of the IBAN
+    /// that the `Bban` was obtained from.
+    #[inline]
+    #[must_use]
+    pub const fn to_iban(&self) -> Iban {
+        Iban(self.0)
+    }
+}
+
+/// Check if the country defines a branch identifier in its BBAN.
+///
+/// Returns `None` if the country is unknown.
+#[must_use]
+pub fn country_supports_branch(country_code: &str) -> Option<bool> {
+    lookup_country(country_code).map(|country| country.branch_identifier.is_some())
+}
+
+/// Check if the country defines national check digits in its BBAN.
+///
+/// Returns `None` if the country is unknown.
+#[must_use]
+pub fn country_supports_checksum(country_code: &str) -> Option<bool> {
+    lookup_country(country_code).map(|country| country.checksum.is_some())
+}
+
+/// Check if a national check algorithm is implemented for the country.
+///
+/// If this returns `Some(true)`, [`Bban::validate_national_checksum`] validates the national
+/// check digits of the country's BBANs (except for accounts that are exempt from the check,
+/// such as those of most Danish banks), otherwise it returns `None` for them.
+///
+/// ```
+/// assert_eq!(iban::country_supports_national_checksum("TN"), Some(true));
+/// assert_eq!(iban::country_supports_national_checksum("DZ"), Some(false));
+/// assert_eq!(iban::country_supports_national_checksum("XX"), None);
+/// ```
+///
+/// Returns `None` if the country is unknown.
+#[must_use]
+pub fn country_supports_national_checksum(country_code: &str) -> Option<bool> {
+    lookup_country(country_code).map(|_| national::is_supported(country_code))
+}
+
+/// Iterate over the countries for which a national check algorithm is implemented.
+///
+/// [`Bban::validate_national_checksum`] validates the national check digits of BBANs of these
+/// countries, and returns `None` for every other country. The countries are yielded in
+/// alphabetical order.
+///
+/// ```
+/// assert!(iban::countries_with_national_check().any(|country_code| country_code == "TN"));
+/// ```
+pub fn countries_with_national_check() -> impl Iterator<Item = &'static str> {
+    national::SUPPORTED.iter().copied()
+}
+
+/// Check if the country code is an official ISO 3166-1 alpha-2 code.
+///
+/// The IBAN registry includes some codes that are not assigned by ISO 3166-1, such as Kosovo's
+/// `XK`. IBANs with these codes are valid and accepted by [`Iban::parse`], but they may be
+/// missing from ISO country tables.
+///
+/// ```
+/// assert!(iban::is_iso3166("DE"));
+/// assert!(!iban::is_iso3166("XK"));
+/// ```
+///
+/// Returns `false` if the country is not in the IBAN registry.
+#[must_use]
+pub fn is_iso3166(country_code: &str) -> bool {
+    lookup_country(country_code).map_or(false, |country| country.iso3166)
+}
+
+/// Get the length of the bank identifier of the country.
+///
+/// Returns `None` if the country is unknown, or does not have a bank identifier.
+///
+/// ```
+/// assert_eq!(iban::bank_id_len("DE"), Some(8));
+/// assert_eq!(iban::bank_id_len("ZZ"), None);
+/// ```
+#[must_use]
+pub fn bank_id_len(country_code: &str) -> Option<usize> {
+    let country = lookup_country(country_code)?;
+    country.bank_identifier.map(|(start, end)| end - start)
+}
+
+/// Get the length of the branch identifier of the country.
+///
+/// Returns `None` if the country is unknown, or does not have a branch identifier.
+///
+/// ```
+/// assert_eq!(iban::branch_id_len("GB"), Some(6));
+/// assert_eq!(iban::branch_id_len("DE"), None);
+/// ```
+#[must_use]
+pub fn branch_id_len(country_code: &str) -> Option<usize> {
+    let country = lookup_country(country_code)?;
+    country.branch_identifier.map(|(start, end)| end - start)
+}
+
+/// Get the length of the BBAN of the country.
+///
+/// This is the length of the country's IBANs, without the country code and check digits.
+///
+/// Returns `None` if the country is unknown.
+///
+/// ```
+/// assert_eq!(iban::bban_length("DE"), Some(18));
+/// assert_eq!(iban::bban_length("ZZ"), None);
+/// ```
+#[must_use]
+pub fn bban_length(country_code: &str) -> Option<usize> {
+    lookup_country(country_code).map(|country| country.length - 4)
+}
+
+/// Suggest the country code that was likely intended, given an unknown country code.
+///
+/// This can be used to improve error messages for [`ParseError::UnknownCountry`].
+/// Only a small number of common mistakes are recognized:
+/// * `UK` (United Kingdom), which should be `GB`
+/// * `EL` (Greece, as used by the European Union), which should be `GR`
+///
+/// The comparison is case-insensitive. Returns `None` if there is no suggestion.
+#[must_use]
+pub fn suggest_country(country_code: &str) -> Option<&'static str> {
+    COUNTRY_CODE_SUGGESTIONS
+        .iter()
+        .find(|(mistake, _)| mistake.eq_ignore_ascii_case(country_code))
+        .map(|&(_, suggestion)| suggestion)
+}
+
+/// Compare the electronic formats of two IBANs character by character.
+///
+/// Yields `(position, old, new)` for each position where the IBANs differ. If the IBANs have
+/// different lengths, the characters past the end of the shorter one are `None`.
+///
+/// ```
+/// let old: iban::Iban = "DE89370400440532013000".parse()?;
+/// let new: iban::Iban = "DE84370400440532013090".parse()?;
+/// let changes = iban::diff(&old, &new).collect::<Vec<_>>();
+/// assert_eq!(changes, [(3, Some('9'), Some('4')), (20, Some('0'), Some('9'))]);
+/// # Ok::<(), iban::ParseError>(())
+/// ```
+pub fn diff(old: &Iban, new: &Iban) -> impl Iterator<Item = (usize, Option<char>, Option<char>)> {
+    let (old, new) = (*old, *new);
+    let char_at =
+        |iban: &Iban, position: usize| iban.as_bytes().get(position).copied().map(char::from);
+
+    (0..old.len().max(new.len()))
+        .map(move |position| (position, char_at(&old, position), char_at(&new, position)))
+        .filter(|(_, old, new)| old != new)
+}
+
+/// Check if the country's BBAN consists only of digits.
+///
+/// Returns `None` if the country is unknown.
+#[must_use]
+pub fn bban_is_numeric(country_code: &str) -> Option<bool> {
+    lookup_country(country_code).map(|country| {
+        bban_character_types(country.format)
+            .all(|character_type| character_type == CharacterType::N)
+    })
+}
+
+/// Check if the BBAN matches the format of the country.
+///
+/// Only the structure of the BBAN is validated, that is its length and the type of each character,
+/// as done by [`Iban::parse`]. As there are no check digits, the checksum is not validated.
+/// Letters may be in either case, but whitespace is not allowed.
+///
+/// Returns `false` if the country is unknown.
+///
+/// ```
+/// assert!(iban::bban_is_valid("DE", "370400440532013000"));
+/// assert!(!iban::bban_is_valid("DE", "37040044053201300"));
+/// ```
+#[must_use]
+pub fn bban_is_valid(country_code: &str, bban: &str) -> bool {
+    country_code.len() == 2
+        && Iban::parse_structure(
+            country_code.bytes().chain(*b"00").chain(bban.bytes()),
+            Case::Preserve,
+        )
+        .is_ok()
+}
+
+/// Parse a string as an Iban, reporting whether the input was in electronic format.
+///
+/// The input is parsed as with [`Iban::parse`]. The returned flag is `true` if the input had to
+/// be normalized, that is if it contained whitespace or letters that were uppercased, and
+/// `false` if the input was already the electronic format of the returned `Iban`.
+///
+/// ```
+/// let (iban, normalized) = iban::parse_and_report("de89 3704 0044 0532 0130 00")?;
+/// assert_eq!(iban.as_str(), "DE89370400440532013000");
+/// assert!(normalized);
+/// # Ok::<(), iban::ParseError>(())
+/// ```
+///
+/// # Errors
+/// This function returns the same errors as [`Iban::parse`].
+pub fn parse_and_report(s: &str) -> Result<(Iban, bool), ParseError> {
+    let iban = Iban::parse(s)?;
+    let normalized = iban.as_str() != s;
+    Ok((iban, normalized))
+}
+
+/// Parse a percent-encoded URL component as an Iban.
+///
+/// IBANs in URL paths and query parameters are often percent-encoded, especially the spaces of
+/// the spaced format (as `%20`, or `+` in query strings). The component is decoded, and the
+/// result is then validated as with [`Iban::parse`].
+///
+/// ```
+/// let iban = iban::parse_url_component("DE89%203704%200044%200532%200130%2000")?;
+/// assert_eq!(iban.as_str(), "DE89370400440532013000");
+/// # Ok::<(), iban::ParseError>(())
+/// ```
+///
+/// # Errors
+/// This function returns the same errors as [`Iban::parse`]. Malformed escapes, and escapes that
+/// decode to anything other than ASCII letters, digits or whitespace, are rejected with
+/// `ParseError::InvalidCharacter` (or `ParseError::CountryCode` or `ParseError::CheckDigit` if
+/// they occur within the first four characters).
+pub fn parse_url_component(s: &str) -> Result<Iban, ParseError> {
+    Iban::parse_bytes(
+        PercentDecode::new(s).filter(|byte| !byte.is_ascii_whitespace()),
+        Case::Preserve,
+    )
+}
+
+/// Get the format of the country's BBAN, for building input masks.
+///
+/// Yields `(count, character_type)` pairs, meaning that the next `count` characters of the BBAN
+/// are of `character_type`. Adjacent segments of the same type are merged, so the pairs describe
+/// the character classes of the BBAN rather than its segments.
+///
+/// Returns `None` if the country is unknown.
+///
+/// ```
+/// use iban::CharacterType;
+///
+/// let mask = iban::input_mask("GB").unwrap().collect::<Vec<_>>();
+/// assert_eq!(mask, [(4, CharacterType::A), (14, CharacterType::N)]);
+/// ```
+pub fn input_mask(country_code: &str) -> Option<impl Iterator<Item = (usize, CharacterType)>> {
+    lookup_country(country_code).map(|country| bban_character_types(country.format).runs())
+}
+
+/// Iterate over the country codes of every supported country.
+///
+/// These are the countries in the IBAN registry, which include a few codes that are not
+/// official ISO 3166-1 codes (see [`is_iso3166`]). The order of the countries is unspecified.
+///
+/// ```
+/// assert!(iban::supported_countries().any(|country_code| country_code == "DE"));
+/// ```
+pub fn supported_countries() -> impl Iterator<Item = &'static str> {
+    COUNTRIES.keys().copied()
+}
+
+/// Iterate over the example IBAN of every supported country.
+///
+/// Yields `(country_code, example)` pairs, where `example` is the electronic-format example
+/// IBAN given for that country in the IBAN registry. The order of the countries is unspecified.
+pub fn all_examples() -> impl Iterator<Item = (&'static str, &'static str)> {
+    COUNTRIES
+        .entries()
+        .map(|(&country_code, country)| (country_code, country.example))
+}
+
+/// Normalizes an IBAN string into its electronic format.
+///
+/// The input is validated as with [`Iban::parse`]. If it is already in electronic
+/// format (without whitespace, and uppercase except in alphanumeric BBAN segments), it is returned as [`Cow::Borrowed`](alloc::borrow::Cow::Borrowed)
+/// without allocating, otherwise the normalized form is returned as [`Cow::Owned`](alloc::borrow::Cow::Owned).
+///
+/// # Errors
+/// Returns a `ParseError` if the input is not a valid IBAN.
+#[cfg(feature = "alloc")]
+pub fn normalize_cow(s: &str) -> Result<alloc::borrow::Cow<'_, str>, ParseError> {
+    use alloc::borrow::{Cow, ToOwned as _};
+
+    let iban = Iban::parse(s)?;
+    if iban.as_str() == s {
+        Ok(Cow::Borrowed(s))
+    } else {
+        Ok(Cow::Owned(iban.as_str().to_owned()))
+    }
+}
+
+/// Validate a string as an IBAN, reporting every problem rather than only the first.
+///
+/// [`Iban::parse`] stops at the first problem it finds. This instead runs each check on its own
+/// and collects all failures, so that a form can show them all at once. The returned errors are in
+/// the order [`Iban::parse`] would report them, and the list is empty if and only if
+/// [`Iban::parse`] succeeds.
+///
+/// Some checks are only meaningful if earlier ones pass, and are skipped otherwise:
+/// * The length and BBAN format can only be checked for a valid, known country code with a
+///   supported format.
+/// * The checksum is only verified if the country code and check digits are well-formed and
+///   every character is valid.
+///
+/// ```
+/// use iban::ParseError;
+///
+/// // Too long, and the extra digit breaks the checksum.
+/// assert_eq!(
+///     iban::diagnose_all("DE89 3704 0044 0532 0130 001"),
+///     [ParseError::TooLong { expected: 22 }, ParseError::WrongChecksum],
+/// );
+/// assert!(iban::diagnose_all("DE89 3704 0044 0532 0130 00").is_empty());
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn diagnose_all(s: &str) -> alloc::vec::Vec<ParseError> {
+    use alloc::vec::Vec;
+
+    // The characters are normalized as by `Iban::parse`: the country code is uppercased here, and
+    // the BBAN once its format is known.
+    let mut characters = s
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect::<Vec<_>>();
+    let prefix_len = characters.len().min(4);
+    characters[..prefix_len].make_ascii_uppercase();
+    let (prefix, bban) = characters.split_at(prefix_len);
+    let mut errors = Vec::new();
+
+    let country_code = prefix
+        .get(..2)
+        .filter(|country_code| country_code.iter().all(u8::is_ascii_uppercase));
+    if country_code.is_none() {
+        errors.push(ParseError::CountryCode);
+    }
+    let check_digits = prefix
+        .get(2..4)
+        .filter(|check_digits| check_digits.iter().all(u8::is_ascii_digit));
+    if check_digits.is_none() {
+        errors.push(ParseError::CheckDigit);
+    }
+    let mut invalid_character = bban.iter().position(|ch| !ch.is_ascii_alphanumeric());
+    let characters_valid = invalid_character.is_none();
+
+    if let Some(country_code) = country_code {
+        let country = core::str::from_utf8(country_code)
+            .ok()
+            .and_then(|country_code| COUNTRIES.get(country_code));
+        match country.map(|country| (country.length, country_format(country))) {
+            None => errors.push(ParseError::UnknownCountry),
+            Some((_, Err(error))) => errors.push(error),
+            Some((expected_length, Ok(format))) => {
+                // Parsing stops at the first character beyond the expected length, so an invalid
+                // character after it is reported after the length.
+                if invalid_character.map_or(false, |position| position + 4 <= expected_length) {
+                    invalid_character = None;
+                    errors.push(ParseError::InvalidCharacter);
+                }
+
+                if characters.len() < expected_length {
+                    errors.push(ParseError::TooShort {
+                        expected: expected_length,
+                    });
+                }
+                if characters.len() > expected_length {
+                    errors.push(ParseError::TooLong {
+                        expected: expected_length,
+                    });
+                }
+                if invalid_character.take().is_some() {
+                    errors.push(ParseError::InvalidCharacter);
+                }
+
+                // Invalid characters are reported separately, and are not reported again here.
+                let invalid_bban = bban.iter().zip(bban_character_types(format)).position(
+                    |(&ch, character_type)| {
+                        ch.is_ascii_alphanumeric()
+                            && !character_type.contains(Case::Preserve.apply(character_type, ch))
+                    },
+                );
+                if let Some(position) = invalid_bban {
+                    errors.push(ParseError::InvalidBban {
+                        position: position + 4,
+                        expected: bban_character_types(format)
+                            .nth(position)
+                            .expect("position is within the format"),
+                    });
+                }
+            }
+        }
     }
+    if invalid_character.is_some() {
+        errors.push(ParseError::InvalidCharacter);
+    }
+
+    let checksum_verifiable = country_code.is_some() && check_digits.is_some() && characters_valid;
+    if checksum_verifiable && calculate_checksum(&characters) != 1 {
+        errors.push(ParseError::WrongChecksum);
+    }
+
+    errors
+}
+
+/// Removes a leading `IBAN` prefix, as done by [`Strictness::Lenient`].
+///
+/// This never changes the meaning of a valid IBAN, as `IB` is not a country code and `AN` are not
+/// check digits.
+fn strip_iban_prefix(s: &str) -> &str {
+    let s = s.trim_start();
+    match s.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("IBAN") => {
+            let s = s[4..].trim_start();
+            s.strip_prefix(':').unwrap_or(s)
+        }
+        _ => s,
+    }
+}
+
+/// Strips double quotes around `s`, and then a single leading apostrophe, as added by spreadsheets.
+fn strip_spreadsheet_quoting(s: &str) -> &str {
+    let s = s.trim_matches(|ch: char| ch.is_whitespace() || is_default_ignorable(ch));
+    let s = s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(s);
+    s.strip_prefix('\'').unwrap_or(s)
+}
+
+/// Discards whitespace, default-ignorable and trailing control characters, as well as spreadsheet
+/// quoting, and maps full-width characters to ASCII, as done by [`Iban::parse_tolerant`].
+fn tolerant_bytes(s: &str) -> impl Iterator<Item = u8> + '_ {
+    strip_spreadsheet_quoting(s.trim_end_matches(char::is_control))
+        .chars()
+        .filter(|&ch| !ch.is_whitespace() && !is_default_ignorable(ch))
+        .map(to_halfwidth)
+        // Any other non-ASCII character is rejected during validation.
+        .map(|ch| u8::try_from(ch).unwrap_or(u8::MAX))
+}
+
+/// Wrapper around an [`Iban`] that is (de)serialized as its separate components.
+///
+/// While an `Iban` is serialized as a single string, this serializes as a structure
+/// with the fields `country`, `check_digits` and `bban` (in electronic format):
+///
+/// ```
+/// use iban::{Iban, StructuredIban};
+///
+/// let iban: Iban = "DE89370400440532013000".parse().unwrap();
+/// let json = serde_json::to_string(&StructuredIban(iban)).unwrap();
+///
+/// assert_eq!(json, r#"{"country":"DE","check_digits":"89","bban":"370400440532013000"}"#);
+/// ```
+///
+/// When deserializing, the components are reassembled and validated as with [`Iban::parse`].
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct StructuredIban(pub Iban);
 
-    /// Get the branch identifier of the BBAN (if it has one).
-    ///
-    /// Returns an `Option` containing a string slice representing the branch identifier,
-    /// or `None` if the BBAN does not have a branch identifier. The branch identifier's position
-    /// and length are determined by the country's IBAN specification.
+#[cfg(feature = "serde")]
+impl From<Iban> for StructuredIban {
     #[inline]
-    #[must_use]
-    pub fn branch_identifier(&self) -> Option<&str> {
-        let (_expected_length, _validation, _bank_offset, branch_offset, _checksum_offset) =
-            COUNTRIES.get(self.country_code())?;
-        branch_offset
-            .as_ref()
-            .copied()
-            .map(|(start, end)| &self[start..end])
+    fn from(iban: Iban) -> Self {
+        Self(iban)
     }
+}
 
-    /// Get the checksum of the BBAN (if it has one).
-    ///
-    /// Returns an `Option` containing a string slice representing the checksum,
-    /// or `None` if the BBAN does not have a checksum. The checksum's position
-    /// and length are determined by the country's IBAN specification.
+#[cfg(feature = "serde")]
+impl From<StructuredIban> for Iban {
     #[inline]
-    #[must_use]
-    pub fn checksum(&self) -> Option<&str> {
-        let (_expected_length, _validation, _bank_offset, _branch_offset, checksum_offset) =
-            COUNTRIES.get(self.country_code())?;
-        checksum_offset
-            .as_ref()
-            .copied()
-            .map(|(start, end)| &self[start..end])
+    fn from(structured: StructuredIban) -> Self {
+        structured.0
     }
+}
 
-    /// Get the BBAN as a string slice.
-    ///
-    /// Returns a reference to the underlying string (electronic-format) that represents the BBAN.
-    #[inline]
-    #[must_use]
-    pub fn as_str(&self) -> &str {
-        self
+/// Generates the check digits for the given country code and BBAN.
+///
+/// The BBAN is validated against the format of the country, as with [`Iban::parse`], and the two
+/// ISO 7064 MOD 97-10 check digits are returned as ASCII characters. Letters in the country code
+/// and BBAN may be in either case.
+///
+/// ```
+/// assert_eq!(iban::generate_check_digits("DE", "370400440532013000"), Ok(*b"89"));
+/// assert_eq!(iban::generate_check_digits("GB", "WEST12345698765432"), Ok(*b"82"));
+/// ```
+///
+/// # Errors
+/// This function returns the same errors as [`Iban::parse`] would for the IBAN with the given
+/// country code and BBAN, except for `ParseError::CheckDigit` and `ParseError::WrongChecksum`.
+pub fn generate_check_digits(country_code: &str, bban: &str) -> Result<[u8; 2], ParseError> {
+    if country_code.len() != 2 {
+        return Err(ParseError::CountryCode);
     }
+
+    let structure = Iban::parse_structure(
+        country_code.bytes().chain(*b"00").chain(bban.bytes()),
+        Case::Preserve,
+    )?;
+    Ok(check_digits(&structure[..2], &structure[4..]))
+}
+
+/// Calculates the check digits for the given country code and BBAN.
+///
+/// The caller must ensure that the BBAN is valid for the country.
+fn check_digits(country_code: &str, bban: &str) -> [u8; 2] {
+    let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
+    iban.push_str(country_code);
+    iban.push_str("00");
+    iban.push_str(bban);
+
+    let check_digits = 98 - calculate_checksum(iban.as_bytes());
+    #[allow(clippy::cast_possible_truncation)]
+    let check_digits = [
+        b'0' + (check_digits / 10) as u8,
+        b'0' + (check_digits % 10) as u8,
+    ];
+    check_digits
 }
 
 /// Calculates the checksum of an IBAN.
@@ -575,6 +2714,9 @@ pub fn calculate_checksum(iban: &[u8]) -> u32 {
                 digits(byte - b'A' + 10)
             }
         })
+        // The checksum is reduced whenever it exceeds 9_999_999, so before each step it is at most
+        // 9_999_999, and after each step at most 99_999_999. This is well within `u32::MAX` for any
+        // number of digits, regardless of whether they came from letters or digits.
         .fold(0u32, |checksum, byte| {
             let checksum = checksum * 10 + u32::from(byte);
             if checksum > 9_999_999 {
@@ -598,6 +2740,16 @@ const _: () = {
         }
     }
 
+    impl serde::Serialize for Bban {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+
     impl<'de> serde::Deserialize<'de> for Iban {
         #[inline]
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -624,6 +2776,88 @@ const _: () = {
             deserializer.deserialize_str(IbanVisitor)
         }
     }
+
+    impl serde::Serialize for StructuredIban {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            use serde::ser::SerializeStruct as _;
+
+            let mut state = serializer.serialize_struct("StructuredIban", 3)?;
+            state.serialize_field("country", self.0.country_code())?;
+            state.serialize_field("check_digits", self.0.check_digits())?;
+            state.serialize_field("bban", self.0.bban().as_str())?;
+            state.end()
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for StructuredIban {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            /// A component of an IBAN, deserialized without allocating.
+            struct Part(ArrayString<IBAN_MAX_LENGTH>);
+
+            impl<'de> serde::Deserialize<'de> for Part {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    struct PartVisitor;
+
+                    impl<'de> serde::de::Visitor<'de> for PartVisitor {
+                        type Value = Part;
+
+                        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                            formatter.write_str("a component of an IBAN")
+                        }
+
+                        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            ArrayString::from(value).map(Part).map_err(|_| {
+                                E::custom(ParseError::TooLong {
+                                    expected: IBAN_MAX_LENGTH,
+                                })
+                            })
+                        }
+                    }
+
+                    deserializer.deserialize_str(PartVisitor)
+                }
+            }
+
+            #[derive(serde::Deserialize)]
+            #[serde(rename = "StructuredIban")]
+            struct Components {
+                country: Part,
+                check_digits: Part,
+                bban: Part,
+            }
+
+            let Components {
+                country,
+                check_digits,
+                bban,
+            } = Components::deserialize(deserializer)?;
+
+            let mut iban = ArrayString::<IBAN_MAX_LENGTH>::new();
+            for part in [country, check_digits, bban] {
+                iban.try_push_str(&part.0).map_err(|_| {
+                    serde::de::Error::custom(ParseError::TooLong {
+                        expected: IBAN_MAX_LENGTH,
+                    })
+                })?;
+            }
+
+            Iban::parse(&iban)
+                .map(StructuredIban)
+                .map_err(serde::de::Error::custom)
+        }
+    }
 };
 
 #[cfg(test)]
@@ -632,7 +2866,11 @@ mod tests {
 
     use test_case::test_case;
 
-    use crate::{digits, CharacterType, Iban, ParseError};
+    use crate::{
+        all_examples, bank_id_len, bban_is_numeric, branch_id_len, calculate_checksum,
+        country_supports_branch, country_supports_checksum, digits, suggest_country, CharacterType,
+        Hint, Iban, Membership, ParseError,
+    };
 
     fn is_clone<T: Clone>(value: &T) {
         let _value = value.clone();
@@ -670,6 +2908,67 @@ mod tests {
         }
     }
 
+    /// Calculates the checksum using wide integers, reducing after every digit.
+    fn reference_checksum(iban: &[u8]) -> u32 {
+        let checksum = iban[4..]
+            .iter()
+            .chain(iban[..4].iter())
+            .map(u8::to_ascii_uppercase)
+            .flat_map(|byte| {
+                if byte.is_ascii_digit() {
+                    digits(byte - b'0')
+                } else {
+                    digits(byte - b'A' + 10)
+                }
+            })
+            .fold(0u128, |checksum, digit| {
+                (checksum * 10 + u128::from(digit)) % 97
+            });
+        u32::try_from(checksum).unwrap()
+    }
+
+    #[test_case(&[b'Z'; 34]; "all letters")]
+    #[test_case(&[b'9'; 34]; "all digits")]
+    #[test_case(b"ZZ99ZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZ"; "letters with check digits")]
+    #[test_case(b"ZZ00Z9Z9Z9Z9Z9Z9Z9Z9Z9Z9Z9Z9Z9Z9Z9"; "mixed")]
+    fn checksum_worst_case(iban: &[u8]) {
+        assert_eq!(calculate_checksum(iban), reference_checksum(iban));
+    }
+
+    /// Classifies a character using the byte predicates, as `CharacterType::contains` did before
+    /// it was table-driven.
+    fn reference_contains(character_type: CharacterType, ch: u8) -> bool {
+        match character_type {
+            CharacterType::N => ch.is_ascii_digit(),
+            CharacterType::A => ch.is_ascii_uppercase(),
+            CharacterType::C => ch.is_ascii_alphanumeric(),
+            CharacterType::I => ch.is_ascii_uppercase() || ch.is_ascii_digit(),
+            CharacterType::S(expected) => ch == expected,
+        }
+    }
+
+    #[test]
+    fn character_type_contains() {
+        let character_types = [
+            CharacterType::N,
+            CharacterType::A,
+            CharacterType::C,
+            CharacterType::I,
+        ]
+        .into_iter()
+        .chain((0..=u8::MAX).map(CharacterType::S));
+
+        for character_type in character_types {
+            for ch in 0..=u8::MAX {
+                assert_eq!(
+                    character_type.contains(ch),
+                    reference_contains(character_type, ch),
+                    "{character_type:?} {ch}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn iban_display_impl() {
         let iban = Iban::parse("AD1200012030200359100100").unwrap();
@@ -679,6 +2978,33 @@ mod tests {
         assert_eq!(iban.to_string().as_str(), "AE07 0331 2345 6789 0123 456");
     }
 
+    #[test]
+    fn iban_display_width() {
+        let iban = Iban::parse("DE89370400440532013000").unwrap();
+        let spaced = "DE89 3704 0044 0532 0130 00";
+
+        assert_eq!(
+            format!("{iban:<42}|"),
+            format!("{spaced}{}|", " ".repeat(15))
+        );
+        assert_eq!(format!("{iban:>30}|"), format!("   {spaced}|"));
+        assert_eq!(format!("{iban:*^31}"), format!("**{spaced}**"));
+        // A width narrower than the spaced IBAN has no effect.
+        assert_eq!(format!("{iban:10}|"), format!("{spaced}|"));
+        assert_eq!(format!("{:>24}", iban.bban()), "  3704 0044 0532 0130 00");
+    }
+
+    #[test]
+    fn iban_display_longest() {
+        let iban = Iban::parse("LC55HEMM000100010012001200023015").unwrap();
+        assert_eq!(iban.to_string().len(), 39);
+        assert_eq!(format!("{iban:<42}|").len(), 43);
+        assert_eq!(
+            format!("{:>24}", iban.bban()),
+            "HEMM 0001 0001 0012 0012 0002 3015"
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn iban_serde() {
@@ -694,6 +3020,111 @@ mod tests {
         assert!(err.to_string().contains("expected an IBAN string"));
     }
 
+    #[cfg(feature = "iso20022")]
+    #[test]
+    fn iso20022_element() {
+        use crate::iso20022::{parse_element, write_element, ElementError};
+
+        const PAIN_001: &str = r#"
+            <Document xmlns="urn:iso:std:iso:20022:tech:xsd:pain.001.001.09">
+              <CstmrCdtTrfInitn>
+                <PmtInf>
+                  <DbtrAcct>
+                    <Id>
+                      <IBAN>
+                        DE89370400440532013000
+                      </IBAN>
+                    </Id>
+                  </DbtrAcct>
+                  <CdtTrfTxInf>
+                    <CdtrAcct>
+                      <Id><IBAN>GB29NWBK6016133192681A</IBAN></Id>
+                    </CdtrAcct>
+                  </CdtTrfTxInf>
+                </PmtInf>
+              </CstmrCdtTrfInitn>
+            </Document>
+        "#;
+
+        let mut texts = PAIN_001.split("<IBAN>").skip(1).map(|rest| {
+            let end = rest.find("</IBAN>").expect("element is closed");
+            &rest[..end]
+        });
+
+        let debtor = texts.next().unwrap();
+        let iban = parse_element("PmtInf/DbtrAcct/Id/IBAN", debtor).unwrap();
+        assert_eq!(iban.as_str(), "DE89370400440532013000");
+
+        let mut xml = String::new();
+        write_element(&mut xml, &iban).unwrap();
+        assert_eq!(xml, "<IBAN>DE89370400440532013000</IBAN>");
+
+        let creditor = texts.next().unwrap();
+        let path = "PmtInf/CdtTrfTxInf/CdtrAcct/Id/IBAN";
+        let err = parse_element(path, creditor).unwrap_err();
+        assert_eq!(
+            err,
+            ElementError {
+                path,
+                error: ParseError::InvalidBban {
+                    position: 21,
+                    expected: CharacterType::N,
+                },
+            }
+        );
+        assert!(err
+            .to_string()
+            .starts_with("invalid IBAN in <PmtInf/CdtTrfTxInf/CdtrAcct/Id/IBAN>: "));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test_case("DE89370400440532013000", "370400440532013000"; "DE")]
+    #[test_case("GB82WEST12345698765432", "WEST12345698765432"; "GB")]
+    fn bban_serde(original: &str, expected: &str) {
+        let iban = Iban::parse(original).unwrap();
+        let json = serde_json::to_string(&iban.bban()).unwrap();
+
+        assert_eq!(json, format!("\"{expected}\""));
+        assert!(!json.contains(iban.country_code()));
+        assert!(!json.contains(' '));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn structured_iban_serde() {
+        use crate::StructuredIban;
+
+        let iban = Iban::parse("DE89370400440532013000").unwrap();
+        let value = serde_json::to_value(StructuredIban(iban)).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "country": "DE",
+                "check_digits": "89",
+                "bban": "370400440532013000",
+            })
+        );
+
+        let structured: StructuredIban = serde_json::from_value(value).unwrap();
+        assert_eq!(Iban::from(structured), iban);
+
+        let err = serde_json::from_value::<StructuredIban>(serde_json::json!({
+            "country": "DE",
+            "check_digits": "88",
+            "bban": "370400440532013000",
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("checksum validation failed"));
+
+        let err = serde_json::from_value::<StructuredIban>(serde_json::json!({
+            "country": "DE",
+            "bban": "370400440532013000",
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("missing field `check_digits`"));
+    }
+
     #[test]
     fn bban_display_impl() {
         let iban = Iban::parse("AD1200012030200359100100").unwrap();
@@ -823,30 +3254,591 @@ mod tests {
     #[test_case("YT3120041010050500013M02606"; "YT")]
     fn iban(original: &str) {
         let iban = Iban::parse(original).expect("iban should be valid");
+        #[cfg(feature = "alloc")]
+        assert!(crate::diagnose_all(original).is_empty());
 
         assert_eq!(iban.country_code(), &original[..2]);
         assert_eq!(iban.check_digits(), &original[2..4]);
         assert_eq!(iban.bban().as_str(), &original[4..]);
         assert_eq!(iban.as_str(), original);
 
-        is_clone(&iban);
-        is_copy(iban);
-        is_debug(&iban);
-        is_display(&iban);
-        is_deref_str(&iban);
-        is_asref_str(&iban);
+        is_clone(&iban);
+        is_copy(iban);
+        is_debug(&iban);
+        is_display(&iban);
+        is_deref_str(&iban);
+        is_asref_str(&iban);
+    }
+
+    #[test_case("DE89370400440532013000", Ok(()), Ok(()), Ok(()); "electronic format")]
+    #[test_case("de89 3704 0044 0532 0130 00", Ok(()), Ok(()), Err(ParseError::CountryCode); "lowercase print format")]
+    #[test_case("DE89\u{A0}3704\u{A0}0044\u{A0}0532\u{A0}0130\u{A0}00", Ok(()), Err(ParseError::InvalidCharacter), Err(ParseError::InvalidCharacter); "non-breaking spaces")]
+    #[test_case("IBAN: DE89370400440532013000", Ok(()), Err(ParseError::CheckDigit), Err(ParseError::CheckDigit); "prefix")]
+    #[test_case("iban DE89 3704 0044 0532 0130 00", Ok(()), Err(ParseError::CheckDigit), Err(ParseError::CountryCode); "lowercase prefix")]
+    #[test_case("IBAN: DE89370400440532013001", Err(ParseError::WrongChecksum), Err(ParseError::CheckDigit), Err(ParseError::CheckDigit); "prefix and wrong checksum")]
+    fn parse_level(
+        input: &str,
+        lenient: Result<(), ParseError>,
+        standard: Result<(), ParseError>,
+        strict: Result<(), ParseError>,
+    ) {
+        use crate::Strictness;
+
+        for (strictness, expected) in [
+            (Strictness::Lenient, lenient),
+            (Strictness::Standard, standard),
+            (Strictness::Strict, strict),
+        ] {
+            let result = Iban::parse_level(input, strictness);
+            assert_eq!(result.map(|_| ()), expected, "{strictness:?}");
+        }
+    }
+
+    #[test_case("DE89\u{200F}370400440532013000"; "right-to-left mark")]
+    #[test_case("\u{200E}DE89 3704 0044 0532 0130 00\u{200E}"; "left-to-right marks")]
+    #[test_case("DE89\u{200B}3704\u{00A0}0044\u{2060}0532013000"; "zero-width and non-breaking spaces")]
+    #[test_case("\u{FEFF}DE89370400440532013000"; "byte order mark")]
+    #[test_case("DE89370400440532013000\u{0}"; "trailing NUL")]
+    #[test_case("\u{FEFF}DE89 3704 0044 0532 0130 00\r\n\u{0}\u{0}"; "byte order mark and trailing control characters")]
+    #[test_case("ＤＥ８９３７０４００４４０５３２０１３０００"; "full-width")]
+    #[test_case("ｄｅ８９\u{3000}３７０４\u{3000}００４４\u{3000}０５３２\u{3000}０１３０\u{3000}００"; "full-width lowercase and spaces")]
+    #[test_case("DE89 3704 ００４４ 0532 0130 00"; "mixed width")]
+    #[test_case("'DE89370400440532013000"; "leading apostrophe")]
+    #[test_case("\"DE89 3704 0044 0532 0130 00\""; "double quotes")]
+    #[test_case("\"'DE89370400440532013000\"\r\n"; "quoted apostrophe")]
+    #[test_case(" ' DE89370400440532013000 "; "apostrophe and spaces")]
+    fn parse_tolerant(input: &str) {
+        assert!(Iban::parse(input).is_err());
+
+        let iban = Iban::parse_tolerant(input).expect("iban is valid");
+        assert_eq!(iban.as_str(), "DE89370400440532013000");
+    }
+
+    #[test_case("\u{202B}SA03 8000 0000 6080 1016 7519\u{202C}"; "right-to-left embedding")]
+    #[test_case("\u{2067}SA03 8000 0000 6080 1016 7519\u{2069}"; "right-to-left isolate")]
+    #[test_case("\u{200F}SA03\u{200E} 8000\u{200E} 0000\u{200E} 6080\u{200E} 1016\u{200E} 7519\u{200F}"; "directional marks")]
+    #[test_case("\u{061C}\u{202A}SA0380000000608010167519\u{202C}\u{061C}"; "arabic letter marks")]
+    fn parse_tolerant_right_to_left(input: &str) {
+        let iban = Iban::parse_tolerant(input).expect("iban is valid");
+        assert_eq!(iban.as_str(), "SA0380000000608010167519");
+    }
+
+    #[test]
+    fn parse_strict_not_tolerant() {
+        for input in [
+            "'DE89370400440532013000",
+            "\"DE89370400440532013000\"",
+            "ＤＥ８９３７０４００４４０５３２０１３０００",
+        ] {
+            assert!(Iban::parse_tolerant(input).is_ok());
+            assert_eq!(Iban::parse_strict(input), Err(ParseError::CountryCode));
+        }
+    }
+
+    #[test]
+    fn parse_tolerant_visual_order() {
+        assert_eq!(
+            Iban::parse_tolerant("\u{200F}7519 1016 6080 0000 8000 SA03"),
+            Err(ParseError::CountryCode)
+        );
+    }
+
+    #[test_case("DE89370400440532013000\u{00E9}", ParseError::InvalidCharacter; "non-ASCII letter")]
+    #[test_case("\u{0394}E89370400440532013000", ParseError::CountryCode; "non-ASCII country code")]
+    #[test_case("DE89\u{0}370400440532013000", ParseError::InvalidCharacter; "inner NUL")]
+    #[test_case("''DE89370400440532013000", ParseError::CountryCode; "two apostrophes")]
+    #[test_case("\"DE89370400440532013000", ParseError::CountryCode; "unbalanced quote")]
+    #[test_case("DE89370400440532013000'", ParseError::InvalidCharacter; "trailing apostrophe")]
+    fn parse_tolerant_error(input: &str, expected_err: ParseError) {
+        assert_eq!(Iban::parse_tolerant(input), Err(expected_err));
+    }
+
+    #[test_case("DE", "370400440532013000", *b"89"; "DE")]
+    #[test_case("GB", "WEST12345698765432", *b"82"; "GB")]
+    #[test_case("gb", "west12345698765432", *b"82"; "lowercase")]
+    #[test_case("NO", "86011117947", *b"93"; "NO")]
+    #[test_case("AT", "1904300234573201", *b"61"; "AT")]
+    #[test_case("SM", "U0322509800000000270100", *b"86"; "SM")]
+    fn generate_check_digits(country_code: &str, bban: &str, expected: [u8; 2]) {
+        assert_eq!(
+            crate::generate_check_digits(country_code, bban),
+            Ok(expected)
+        );
+    }
+
+    #[test_case("DEU", "370400440532013000", ParseError::CountryCode; "long country code")]
+    #[test_case("D", "370400440532013000", ParseError::CountryCode; "short country code")]
+    #[test_case("XX", "370400440532013000", ParseError::UnknownCountry; "unknown country")]
+    #[test_case("DE", "37040044053201300", ParseError::TooShort { expected: 22 }; "short bban")]
+    #[test_case("DE", "3704004405320130000", ParseError::TooLong { expected: 22 }; "long bban")]
+    #[test_case("DE", "37040044053201300A", ParseError::InvalidBban { position: 21, expected: CharacterType::N }; "invalid bban")]
+    fn generate_check_digits_error(country_code: &str, bban: &str, expected_err: ParseError) {
+        assert_eq!(
+            crate::generate_check_digits(country_code, bban),
+            Err(expected_err)
+        );
+    }
+
+    #[test_case("NO9386011117947", 4, 3; "not a multiple of four")]
+    #[test_case("DE89370400440532013000", 6, 2; "two remaining")]
+    #[test_case("BE68539007547034", 4, 4; "multiple of four")]
+    #[test_case("LC55HEMM000100010012001200023015", 8, 4; "longest multiple of four")]
+    fn group_layout(input: &str, group_count: usize, last_group_len: usize) {
+        let iban = Iban::parse(input).unwrap();
+
+        assert_eq!(iban.group_count(), group_count);
+        assert_eq!(iban.last_group_len(), last_group_len);
+
+        let spaced = iban.to_string();
+        let groups = spaced.split(' ').collect::<Vec<_>>();
+        assert_eq!(groups.len(), group_count);
+        assert_eq!(groups.last().unwrap().len(), last_group_len);
+
+        let spaces = spaced.match_indices(' ').map(|(index, _)| index);
+        assert!(iban.separator_positions().eq(spaces));
+    }
+
+    #[test]
+    fn separator_positions() {
+        let iban = Iban::parse("DE89370400440532013000").unwrap();
+        assert_eq!(
+            iban.separator_positions().collect::<Vec<_>>(),
+            [4, 9, 14, 19, 24]
+        );
+    }
+
+    #[test_case("89DE370400440532013000"; "electronic")]
+    #[test_case("89DE 3704 0044 0532 0130 00"; "spaced")]
+    #[test_case("89de370400440532013000"; "lowercase")]
+    fn parse_verbose_swapped(input: &str) {
+        let diagnostic = Iban::parse_verbose(input).unwrap_err();
+        let expected = Iban::parse("DE89370400440532013000").unwrap();
+
+        assert_eq!(diagnostic.error, ParseError::CountryCode);
+        assert_eq!(
+            diagnostic.hint,
+            Some(Hint::SwappedCountryAndCheckDigits(expected))
+        );
+        assert!(diagnostic
+            .to_string()
+            .contains("country code and check digits may be swapped"));
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("   "; "spaces")]
+    #[test_case("\t\r\n"; "whitespace")]
+    #[test_case("\u{FEFF}\u{200B}"; "invisible")]
+    #[test_case("\"\""; "empty spreadsheet cell")]
+    fn parse_verbose_empty(input: &str) {
+        let diagnostic = Iban::parse_verbose(input).unwrap_err();
+
+        assert_eq!(diagnostic.error, ParseError::CountryCode);
+        assert_eq!(diagnostic.hint, Some(Hint::Empty));
+        assert!(diagnostic.to_string().ends_with("the input is empty"));
+    }
+
+    #[test_case("DEDE89370400440532013000"; "electronic")]
+    #[test_case("DE DE89 3704 0044 0532 0130 00"; "spaced")]
+    #[test_case("deDE89370400440532013000"; "mixed case")]
+    fn parse_verbose_duplicated(input: &str) {
+        let diagnostic = Iban::parse_verbose(input).unwrap_err();
+        let expected = Iban::parse("DE89370400440532013000").unwrap();
+
+        assert_eq!(diagnostic.error, ParseError::CheckDigit);
+        assert_eq!(diagnostic.hint, Some(Hint::DuplicatedCountryCode(expected)));
+        assert!(diagnostic.to_string().ends_with(
+            "country code may be duplicated (did you mean DE89 3704 0044 0532 0130 00?)"
+        ));
+    }
+
+    #[test]
+    fn parse_verbose_duplicated_longest() {
+        let expected = Iban::parse("LC55HEMM000100010012001200023015").unwrap();
+        let input = format!("LC{expected}");
+        assert_eq!(
+            Iban::parse_verbose(&input).unwrap_err().hint,
+            Some(Hint::DuplicatedCountryCode(expected))
+        );
+    }
+
+    #[test]
+    fn parse_detailed() {
+        let parsed = Iban::parse_detailed("de89 3704 0044 0532 0130 00").unwrap();
+        assert_eq!(
+            parsed,
+            crate::ParsedIban {
+                iban: Iban::parse("DE89370400440532013000").unwrap(),
+                country_name: "Germany",
+                sepa: true,
+                normalized: true,
+            }
+        );
+
+        let parsed = Iban::parse_detailed("BR9700360305000010009795493P1").unwrap();
+        assert_eq!(parsed.country_name, "Brazil");
+        assert!(!parsed.sepa);
+        assert!(!parsed.normalized);
+
+        assert_eq!(
+            Iban::parse_detailed("DE88370400440532013000"),
+            Err(ParseError::WrongChecksum)
+        );
+    }
+
+    #[test_case("DE8937040044053201300", ParseError::TooShort { expected: 22 }, 21; "too short")]
+    #[test_case("de89 3704 0044 0532 0130 000", ParseError::TooLong { expected: 22 }, 23; "too long")]
+    #[test_case("DE89370400440532013000000000000000000000", ParseError::TooLong { expected: 22 }, 40; "beyond maximum length")]
+    fn parse_verbose_wrong_length(input: &str, expected_err: ParseError, length: usize) {
+        let diagnostic = Iban::parse_verbose(input).unwrap_err();
+
+        assert_eq!(diagnostic.error, expected_err);
+        assert_eq!(
+            diagnostic.hint,
+            Some(Hint::WrongLength {
+                country_code: "DE",
+                expected: 22,
+                length,
+            })
+        );
+        assert!(diagnostic.to_string().ends_with(&format!(
+            "DE IBANs have 22 characters, but {length} were given"
+        )));
+    }
+
+    #[test_case("DE88370400440532013000", ParseError::WrongChecksum; "wrong checksum")]
+    #[test_case("88DE370400440532013000", ParseError::CountryCode; "swapped with wrong checksum")]
+    #[test_case("89D", ParseError::CountryCode; "too short to swap")]
+    #[test_case("DEDE88370400440532013000", ParseError::CheckDigit; "duplicated with wrong checksum")]
+    #[test_case("DEDE", ParseError::CheckDigit; "only duplicated country code")]
+    fn parse_verbose_without_hint(input: &str, expected_err: ParseError) {
+        let diagnostic = Iban::parse_verbose(input).unwrap_err();
+
+        assert_eq!(diagnostic.error, expected_err);
+        assert_eq!(diagnostic.hint, None);
+        assert_eq!(diagnostic.to_string(), expected_err.to_string());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test_case("DE89370400440532013000", 11; "numeric")]
+    #[test_case("EG380019000500000000263180002", 14; "longest numeric")]
+    #[test_case("GB82WEST12345698765432", 15; "alphanumeric")]
+    #[test_case("LC55HEMM000100010012001200023015", 22; "longest alphanumeric")]
+    #[test_case("QA58DOHB00001234567890abcdefg", 29; "lowercase")]
+    #[test_case("XK051212012345678906", 10; "XK")]
+    fn pack(original: &str, len: usize) {
+        let iban = Iban::parse(original).unwrap();
+        let packed = iban.pack();
+        assert_eq!(packed.len(), len);
+        assert_eq!(Iban::unpack(&packed), Ok(iban));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn unpack_corrupted() {
+        let iban = Iban::parse("DE89370400440532013000").unwrap();
+        let packed = iban.pack();
+
+        assert_eq!(Iban::unpack(&[]), Err(ParseError::CountryCode));
+        assert_eq!(Iban::unpack(&[0xff, 0xff]), Err(ParseError::CountryCode));
+        // The header of `ZZ`, which is not in the registry.
+        assert_eq!(Iban::unpack(&[0x07, 0xe9]), Err(ParseError::UnknownCountry));
+        assert_eq!(
+            Iban::unpack(&packed[..packed.len() - 1]),
+            Err(ParseError::TooShort { expected: 22 })
+        );
+        assert_eq!(
+            Iban::unpack(&[&packed[..], &[0]].concat()),
+            Err(ParseError::TooLong { expected: 22 })
+        );
+        assert_eq!(
+            Iban::unpack(&[&packed[..2], &[0xff; 9]].concat()),
+            Err(ParseError::InvalidCharacter)
+        );
+
+        let mut flipped = packed.clone();
+        *flipped.last_mut().unwrap() ^= 1;
+        assert_eq!(Iban::unpack(&flipped), Err(ParseError::WrongChecksum));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn normalize_cow() {
+        use std::borrow::Cow;
+
+        let normalized = crate::normalize_cow("DE89370400440532013000").unwrap();
+        assert!(matches!(
+            normalized,
+            Cow::Borrowed("DE89370400440532013000")
+        ));
+
+        let normalized = crate::normalize_cow("DE89 3704 0044 0532 0130 00").unwrap();
+        assert!(matches!(normalized, Cow::Owned(ref owned) if owned == "DE89370400440532013000"));
+
+        let normalized = crate::normalize_cow("de89370400440532013000").unwrap();
+        assert!(matches!(normalized, Cow::Owned(ref owned) if owned == "DE89370400440532013000"));
+
+        assert_eq!(
+            crate::normalize_cow("DE89370400440532013001"),
+            Err(ParseError::WrongChecksum)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test_case("DE89 3704 0044 0532 0130 00", &[]; "valid")]
+    #[test_case("DE00 3704 0044 0532 0130 0X", &[
+        ParseError::InvalidBban { position: 21, expected: CharacterType::N },
+        ParseError::WrongChecksum,
+    ]; "invalid bban and checksum")]
+    #[test_case("DE8! 3704 0044 0532 0130 001", &[
+        ParseError::CheckDigit,
+        ParseError::TooLong { expected: 22 },
+    ]; "invalid check digits and too long")]
+    #[test_case("D889 37!4 0044 0532 0130", &[
+        ParseError::CountryCode,
+        ParseError::InvalidCharacter,
+    ]; "invalid country code and character")]
+    #[test_case("XX00 3704 0044 0532 0130 00", &[
+        ParseError::UnknownCountry,
+        ParseError::WrongChecksum,
+    ]; "unknown country")]
+    #[test_case("DE0", &[ParseError::CheckDigit, ParseError::TooShort { expected: 22 }]; "truncated")]
+    #[test_case("XX00 3704 0044 0532 0130 0!", &[
+        ParseError::UnknownCountry,
+        ParseError::InvalidCharacter,
+    ]; "unknown country and invalid character")]
+    #[test_case("DE89 3704 0044 0532 0130 00 0!", &[
+        ParseError::TooLong { expected: 22 },
+        ParseError::InvalidCharacter,
+    ]; "invalid character beyond expected length")]
+    fn diagnose_all(input: &str, expected: &[ParseError]) {
+        let errors = crate::diagnose_all(input);
+        assert_eq!(errors, expected);
+        assert_eq!(errors.first().copied(), Iban::parse(input).err());
+    }
+
+    #[test_case("qa58dohb00001234567890AbcDefG", "QA58DOHB00001234567890AbcDefG"; "QA")]
+    #[test_case("bh94bmag0000129912aBcD", "BH94BMAG0000129912aBcD"; "BH")]
+    #[test_case("BL68 2004 1010 0505 0001 3m02 606", "BL6820041010050500013m02606"; "BL")]
+    #[test_case("bl68 2004 1010 0505 0001 3m02 606", "BL6820041010050500013m02606"; "BL lowercase country code")]
+    #[test_case("gb82 west 1234 5698 7654 32", "GB82WEST12345698765432"; "GB alphabetic segment")]
+    #[test_case("aa110011123z5678", "AA110011123Z5678"; "AA upper case alphanumeric segment")]
+    fn parse_preserves_case(input: &str, expected: &str) {
+        assert_eq!(
+            Iban::parse_uppercase(input).map(|iban| iban.to_string()),
+            Iban::parse(&expected.to_ascii_uppercase()).map(|iban| iban.to_string())
+        );
+
+        let iban = Iban::parse(input).expect("iban is valid");
+        assert_eq!(iban.as_str(), expected);
+        #[cfg(feature = "alloc")]
+        assert!(crate::diagnose_all(input).is_empty());
+    }
+
+    #[test_case("QA58DOHB00001234567890AbcDefG"; "QA")]
+    #[test_case("BH94BMAG0000129912aBcD"; "BH")]
+    fn parse_case_modes(input: &str) {
+        let preserved = Iban::parse(input).expect("iban is valid");
+        assert_eq!(preserved.as_str(), input);
+        let spaced = preserved.to_string();
+        assert_eq!(Iban::parse(&spaced), Ok(preserved));
+
+        let uppercased = Iban::parse_uppercase(input).expect("iban is valid");
+        assert_eq!(uppercased.as_str(), input.to_ascii_uppercase());
+        let spaced = uppercased.to_string();
+        assert_eq!(Iban::parse_uppercase(&spaced), Ok(uppercased));
+        assert_ne!(uppercased, preserved);
+    }
+
+    #[test_case("/DE89370400440532013000/"; "slashes")]
+    #[test_case("DE89370400440532013000"; "plain")]
+    #[test_case("/DE89370400440532013000\r\nJOHN DOE\r\nBERLIN"; "name and address")]
+    fn parse_field(input: &str) {
+        let iban = Iban::parse_field(input).expect("iban is valid");
+        assert_eq!(iban.as_str(), "DE89370400440532013000");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test_case("DE89370400440532013000", "/DE89370400440532013000"; "DE")]
+    #[test_case("NO9386011117947", "/NO9386011117947"; "NO")]
+    fn to_mt_account(input: &str, expected: &str) {
+        let iban = Iban::parse(input).expect("iban is valid");
+        let account = iban.to_mt_account();
+
+        assert_eq!(account, expected);
+        assert_eq!(Iban::parse_field(&format!("{account}\nJOHN DOE")), Ok(iban));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test_case("DE89 3704 0044 0532 0130 00"; "DE")]
+    #[test_case("QA58DOHB00001234567890abcdefg"; "QA")]
+    fn to_shared(input: &str) {
+        let iban = Iban::parse(input).expect("iban is valid");
+        assert_eq!(&*iban.to_shared(), iban.as_str());
+    }
+
+    #[test_case("DE00370400440532013000"; "zeroed")]
+    #[test_case("DE12 3704 0044 0532 0130 00"; "wrong")]
+    #[test_case("DE89370400440532013000"; "already valid")]
+    fn repair_check_digits(input: &str) {
+        let iban = Iban::repair_check_digits(input).expect("iban is structurally valid");
+        assert_eq!(iban.as_str(), "DE89370400440532013000");
+        assert_eq!(Iban::parse(&iban), Ok(iban));
+    }
+
+    #[test_case("DE0037040044053201300", ParseError::TooShort { expected: 22 }; "too short")]
+    #[test_case("ZZ00370400440532013000", ParseError::UnknownCountry; "unknown country")]
+    #[test_case("GB00NWBK6016133192681A", ParseError::InvalidBban { position: 21, expected: CharacterType::N }; "invalid bban")]
+    fn repair_check_digits_error(input: &str, expected_err: ParseError) {
+        assert_eq!(Iban::repair_check_digits(input), Err(expected_err));
+    }
+
+    #[test_case("DE89 3704 0044 0532 0130 00"; "DE")]
+    #[test_case("qa58dohb00001234567890abcdefg"; "QA")]
+    fn as_bytes(input: &str) {
+        let iban = Iban::parse(input).expect("iban is valid");
+        assert_eq!(iban.as_bytes(), iban.as_str().as_bytes());
+
+        let bban = iban.bban();
+        assert_eq!(bban.as_bytes(), bban.as_str().as_bytes());
+        assert_eq!(bban.as_bytes(), &iban.as_bytes()[4..]);
+    }
+
+    #[test]
+    fn into_inner_round_trip() {
+        let iban = Iban::parse("DE89370400440532013000").expect("iban should be valid");
+
+        let inner = iban.into_inner();
+        assert_eq!(inner.as_str(), "DE89370400440532013000");
+
+        // SAFETY: `inner` was obtained from a valid `Iban`.
+        let new_iban = unsafe { Iban::from_inner_unchecked(inner) };
+        assert_eq!(iban, new_iban);
+
+        let iban = Iban::parse("QA58DOHB00001234567890AbcDefG").unwrap();
+        // SAFETY: `inner` was obtained from a valid `Iban`.
+        let new_iban = unsafe { Iban::from_inner_unchecked(iban.into_inner()) };
+        assert_eq!(iban, new_iban);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test_case("de89370400440532013000"; "lowercase country code")]
+    #[test_case("DE8937040044053201300a"; "lowercase in numeric segment")]
+    #[test_case("GB82west12345698765432"; "lowercase in alphabetic segment")]
+    #[test_case("DE89 3704 0044 0532 0130 00"; "print format")]
+    #[test_case("DE"; "too short")]
+    #[should_panic = "buffer is not an IBAN in electronic format"]
+    fn from_inner_unchecked_invalid(inner: &str) {
+        let inner = arrayvec::ArrayString::from(inner).unwrap();
+        // SAFETY: Not upheld, which must trip the debug assertion.
+        let _iban = unsafe { Iban::from_inner_unchecked(inner) };
+    }
+
+    #[test_case("GB00 WEST 1234 5698 7654 32", Some("WEST"), Some("123456"); "GB")]
+    #[test_case("DE00370400440532013000", Some("37040044"), None; "DE")]
+    #[test_case("fr0020041010050500013m02606", Some("20041"), Some("01005"); "FR lowercase")]
+    fn parse_unchecked_wrong_checksum(input: &str, bank: Option<&str>, branch: Option<&str>) {
+        assert_eq!(Iban::parse(input), Err(ParseError::WrongChecksum));
+
+        let iban = Iban::parse_unchecked(input).expect("iban is structurally valid");
+        assert_eq!(iban.check_digits(), "00");
+        assert_eq!(iban.bban().bank_identifier(), bank);
+        assert_eq!(iban.bban().branch_identifier(), branch);
+    }
+
+    #[test_case("GB00 WEST 1234 5698 7654 3", ParseError::TooShort { expected: 22 }; "too short")]
+    #[test_case("GB00 WEST 1234 5698 7654 3X", ParseError::InvalidBban { position: 21, expected: CharacterType::N }; "invalid bban")]
+    #[test_case("XX00 WEST 1234 5698 7654 32", ParseError::UnknownCountry; "unknown country")]
+    fn parse_unchecked_error(input: &str, expected_err: ParseError) {
+        assert_eq!(Iban::parse_unchecked(input), Err(expected_err));
+    }
+
+    // Each input is broken in several ways, and the earliest stage's error must be reported.
+    #[test_case("1E00 3704 0044 0532 0130 0X!", ParseError::CountryCode; "country code over everything")]
+    #[test_case("XXA0 3704 0044 0532 0130 0X!", ParseError::CheckDigit; "check digits over unknown country")]
+    #[test_case("DE8X 3704", ParseError::CheckDigit; "check digits over length")]
+    #[test_case("XX00 3704 0044 0532 0130 0X!", ParseError::UnknownCountry; "unknown country over characters")]
+    #[test_case("DE00 X704 0044 0532 0130 00!", ParseError::InvalidCharacter; "invalid character over bban and length")]
+    #[test_case("DE00 X704 0044 0532 0130 0", ParseError::TooShort { expected: 22 }; "too short over bban")]
+    #[test_case("DE00 X704 0044 0532 0130 000", ParseError::TooLong { expected: 22 }; "too long over bban")]
+    #[test_case("DE00 X704 0044 0532 0130 0X", ParseError::InvalidBban { position: 4, expected: CharacterType::N }; "first bban position over later ones")]
+    #[test_case("DE00 3704 0044 0532 0130 0X", ParseError::InvalidBban { position: 21, expected: CharacterType::N }; "bban over checksum")]
+    #[test_case("DE00 3704 0044 0532 0130 000!", ParseError::TooLong { expected: 22 }; "parsing stops beyond expected length")]
+    fn parse_error_precedence(input: &str, expected_err: ParseError) {
+        assert_eq!(Iban::parse(input), Err(expected_err));
+        #[cfg(feature = "alloc")]
+        assert_eq!(crate::diagnose_all(input).first(), Some(&expected_err));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn parse_into_vec() {
+        let mut out = Vec::new();
+
+        let inputs = [
+            "DE89 3704 0044 0532 0130 00",
+            "gb82west12345698765432",
+            "NO9386011117947",
+        ];
+        for input in inputs {
+            let iban = Iban::parse_into_vec(input, &mut out).unwrap();
+            assert_eq!(Ok(iban), Iban::parse(input));
+        }
+        assert_eq!(
+            out,
+            b"DE89370400440532013000GB82WEST12345698765432NO9386011117947"
+        );
+
+        let len = out.len();
+        assert_eq!(
+            Iban::parse_into_vec("DE89370400440532013001", &mut out),
+            Err(ParseError::WrongChecksum)
+        );
+        assert_eq!(out.len(), len);
+    }
+
+    #[test]
+    fn parse_into_reused_scratch() {
+        let mut scratch = arrayvec::ArrayString::new();
+
+        let inputs = [
+            (
+                "LC55 HEMM 0001 0001 0012 0012 0002 3015",
+                Ok("LC55HEMM000100010012001200023015"),
+            ),
+            ("de89370400440532013000", Ok("DE89370400440532013000")),
+            ("DE89370400440532013001", Err(ParseError::WrongChecksum)),
+            (
+                "GB82WEST1234569876543",
+                Err(ParseError::TooShort { expected: 22 }),
+            ),
+            ("NO9386011117947", Ok("NO9386011117947")),
+        ];
+        for (input, expected) in inputs {
+            let result = Iban::parse_into(input, &mut scratch);
+            assert_eq!(result, Iban::parse(input), "{input}");
+            assert_eq!(
+                result.as_ref().map(Iban::as_str),
+                expected.as_deref(),
+                "{input}"
+            );
+            if let Ok(iban) = result {
+                assert_eq!(scratch.as_str(), iban.as_str());
+            }
+        }
     }
 
     #[test_case("1T4120041010050500013M02606", ParseError::CountryCode; "country code")]
     #[test_case("YTa120041010050500013M02606", ParseError::CheckDigit; "check digit")]
     #[test_case("ZZ18SSCB11010000000000001497USD", ParseError::UnknownCountry; "unknown country")]
     #[test_case("YT412*041010050500013M02606", ParseError::InvalidCharacter; "invalid character")]
-    #[test_case("SC18SSCB11010000000000001497USDABCD", ParseError::InvalidLength; "too long")]
-    #[test_case("YT3120041010050500013M0260a", ParseError::InvalidBban; "invalid bban")]
-    #[test_case("AA110011123Z567", ParseError::InvalidLength; "too short")]
+    #[test_case("SC18SSCB11010000000000001497USDABCD", ParseError::TooLong { expected: 31 }; "too long")]
+    #[test_case("YT3120041010050500013M0260a", ParseError::InvalidBban { position: 26, expected: CharacterType::N }; "invalid bban")]
+    #[test_case("AA110011123Z567", ParseError::TooShort { expected: 16 }; "too short")]
     #[test_case("YT4120041010050500013M02606", ParseError::WrongChecksum; "wrong checksum")]
     fn parse_error(iban: &str, expected_err: ParseError) {
         assert_eq!(Iban::parse(iban), Err(expected_err));
+        #[cfg(feature = "alloc")]
+        assert_eq!(crate::diagnose_all(iban).first(), Some(&expected_err));
 
         is_clone(&expected_err);
         is_copy(expected_err);
@@ -866,6 +3858,11 @@ mod tests {
     #[test_case("AA110011123Z5678", Some("0011"), None, None; "AA")]
     #[test_case("BE68539007547034", Some("539"), None, Some("34"); "BE")]
     #[test_case("IQ98NBIQ850123456789012", Some("NBIQ"), Some("850"), None; "IQ")]
+    #[test_case("GB29NWBK60161331926819", Some("NWBK"), Some("601613"), None; "GB")]
+    #[test_case("IE29AIBK93115212345678", Some("AIBK"), Some("931152"), None; "IE")]
+    #[test_case("LV80BANK0000435195001", Some("BANK"), None, None; "LV")]
+    #[test_case("LT121000011101001000", Some("10000"), None, None; "LT")]
+    #[test_case("EE382200221020145685", Some("22"), None, Some("5"); "EE")]
     fn bban(original: &str, bank: Option<&str>, branch: Option<&str>, checksum: Option<&str>) {
         let iban = Iban::parse(original).expect("iban is valid");
         let bban = iban.bban();
@@ -883,6 +3880,733 @@ mod tests {
         is_asref_str(&bban);
     }
 
+    #[test_case("BE68539007547034", "0075470"; "BE")]
+    #[test_case("FR1420041010050500013M02606", "0500013M026"; "FR")]
+    #[test_case("GB29NWBK60161331926819", "31926819"; "GB")]
+    #[test_case("DE89370400440532013000", "0532013000"; "DE")]
+    #[test_case("NO9386011117947", "111794"; "NO")]
+    fn bban_residual(original: &str, expected: &str) {
+        let iban = Iban::parse(original).expect("iban is valid");
+        assert_eq!(iban.bban().residual(), expected);
+    }
+
+    #[test_case("NO9386011117947", Some(86_011_117_947); "NO")]
+    #[test_case("DE89370400440532013000", Some(370_400_440_532_013_000); "DE")]
+    #[test_case("GB29NWBK60161331926819", None; "GB")]
+    fn bban_as_u128(original: &str, expected: Option<u128>) {
+        let iban = Iban::parse(original).expect("iban is valid");
+        assert_eq!(iban.bban().as_u128(), expected);
+    }
+
+    #[test_case("DE89370400440532013000"; "DE")]
+    #[test_case("GB29NWBK60161331926819"; "GB")]
+    #[test_case("QA58DOHB00001234567890ABCDEFG"; "QA")]
+    fn bban_to_iban(original: &str) {
+        let iban = Iban::parse(original).expect("iban is valid");
+        assert_eq!(iban.bban().to_iban(), iban);
+    }
+
+    #[test]
+    fn bban_chars_with_types() {
+        let iban = Iban::parse("GB29NWBK60161331926819").expect("iban is valid");
+        let bban = iban.bban();
+        let types = bban.chars_with_types().collect::<Vec<_>>();
+
+        assert_eq!(types.len(), bban.len());
+        assert!(types[..4]
+            .iter()
+            .all(|&(_, character_type)| character_type == CharacterType::A));
+        assert!(types[4..]
+            .iter()
+            .all(|&(_, character_type)| character_type == CharacterType::N));
+        assert_eq!(
+            types.iter().map(|&(ch, _)| ch).collect::<String>(),
+            bban.as_str()
+        );
+    }
+
+    #[test_case("CH9300762011623852957", Some("762"); "CH")]
+    #[test_case("PT50000201231234567890154", Some("0002"); "PT fixed width")]
+    #[test_case("DE89370400440532013000", Some("37040044"); "DE")]
+    #[test_case("GB29NWBK60161331926819", Some("NWBK"); "GB")]
+    fn bank_identifier_trimmed(original: &str, expected: Option<&str>) {
+        let iban = Iban::parse(original).expect("iban is valid");
+        assert_eq!(iban.bban().bank_identifier_trimmed(), expected);
+    }
+
+    #[test_case("UK", Some("GB"); "UK")]
+    #[test_case("el", Some("GR"); "lowercase EL")]
+    #[test_case("QQ", None; "unrelated")]
+    #[test_case("GB", None; "valid")]
+    fn country_suggestion(country_code: &str, expected: Option<&str>) {
+        assert_eq!(suggest_country(country_code), expected);
+    }
+
+    #[test_case("NO", Some(true); "NO")]
+    #[test_case("GB", Some(false); "GB")]
+    #[test_case("ZZ", None; "unknown")]
+    fn numeric_bban(country_code: &str, expected: Option<bool>) {
+        assert_eq!(bban_is_numeric(country_code), expected);
+    }
+
+    #[test]
+    fn unsupported_format() {
+        let unsupported = crate::CountryInfo {
+            name: "",
+            length: 18,
+            format: &[],
+            bank_identifier: None,
+            branch_identifier: None,
+            checksum: None,
+            sepa: false,
+            iso3166: false,
+            example: "",
+            membership: Membership::NonMember,
+        };
+        assert_eq!(
+            crate::country_format(&unsupported),
+            Err(ParseError::UnsupportedFormat)
+        );
+
+        // Every country in the registry currently has a supported format.
+        for (country_code, country) in crate::COUNTRIES.entries() {
+            assert!(
+                crate::country_format(country).is_ok(),
+                "{country_code} has an unsupported format"
+            );
+        }
+    }
+
+    #[test_case("GB", &[(4, CharacterType::A), (14, CharacterType::N)]; "GB")]
+    #[test_case("de", &[(18, CharacterType::N)]; "lowercase DE")]
+    #[test_case("QA", &[(4, CharacterType::A), (21, CharacterType::C)]; "QA")]
+    #[test_case("MU", &[(4, CharacterType::A), (19, CharacterType::N), (3, CharacterType::A)]; "MU")]
+    fn input_mask(country_code: &str, expected: &[(usize, CharacterType)]) {
+        let mask = crate::input_mask(country_code).unwrap().collect::<Vec<_>>();
+        assert_eq!(mask, expected);
+
+        let length = mask.iter().map(|&(count, _)| count).sum::<usize>();
+        let example = all_examples()
+            .find(|&(code, _)| code.eq_ignore_ascii_case(country_code))
+            .map(|(_, example)| example)
+            .unwrap();
+        assert_eq!(length + 4, example.len());
+    }
+
+    #[test]
+    fn input_mask_unknown() {
+        assert!(crate::input_mask("ZZ").is_none());
+    }
+
+    #[test_case("DE89370400440532013000", false; "electronic format")]
+    #[test_case("QA58DOHB00001234567890abcdefg", false; "preserved lowercase")]
+    #[test_case("DE89 3704 0044 0532 0130 00", true; "spaced")]
+    #[test_case("de89370400440532013000", true; "lowercase")]
+    #[test_case("gb82 west 1234 5698 7654 32", true; "spaced lowercase")]
+    fn parse_and_report(input: &str, expected: bool) {
+        let (iban, normalized) = crate::parse_and_report(input).expect("iban is valid");
+        assert_eq!(iban, Iban::parse(input).unwrap());
+        assert_eq!(normalized, expected);
+    }
+
+    #[test_case("DE89%203704%200044%200532%200130%2000"; "encoded spaces")]
+    #[test_case("DE89+3704+0044+0532+0130+00"; "plus as space")]
+    #[test_case("%44%45%38%39370400440532013000"; "encoded characters")]
+    #[test_case("de89%203704%200044%200532%200130%2000"; "lowercase")]
+    #[test_case("DE89370400440532013000"; "not encoded")]
+    fn parse_url_component(input: &str) {
+        let iban = crate::parse_url_component(input).expect("iban is valid");
+        assert_eq!(iban.as_str(), "DE89370400440532013000");
+    }
+
+    #[test_case("DE89%2F370400440532013000", ParseError::InvalidCharacter; "encoded slash")]
+    #[test_case("DE89370400440532013000%C3%A9", ParseError::InvalidCharacter; "encoded non-ASCII")]
+    #[test_case("DE89%2370400440532013000", ParseError::InvalidCharacter; "malformed escape")]
+    #[test_case("DE89370400440532013000%2", ParseError::InvalidCharacter; "truncated escape")]
+    #[test_case("%ZZE89370400440532013000", ParseError::CountryCode; "malformed country code")]
+    fn parse_url_component_error(input: &str, expected_err: ParseError) {
+        assert_eq!(crate::parse_url_component(input), Err(expected_err));
+    }
+
+    #[test_case("DE", "370400440532013000", true; "DE")]
+    #[test_case("de", "370400440532013000", true; "lowercase country code")]
+    #[test_case("GB", "west12345698765432", true; "lowercase GB")]
+    #[test_case("DE", "37040044053201300", false; "DE too short")]
+    #[test_case("DE", "3704004405320130000", false; "DE too long")]
+    #[test_case("DE", "37040044053201300A", false; "DE letter")]
+    #[test_case("DE", "3704 0044 0532 0130 00", false; "spaced")]
+    #[test_case("DE", "", false; "empty")]
+    #[test_case("ZZ", "370400440532013000", false; "unknown country")]
+    #[test_case("DEU", "370400440532013000", false; "long country code")]
+    fn bban_is_valid(country_code: &str, bban: &str, expected: bool) {
+        assert_eq!(crate::bban_is_valid(country_code, bban), expected);
+    }
+
+    #[test_case("IQ", Some(4), Some(3); "IQ")]
+    #[test_case("DE", Some(8), None; "DE")]
+    #[test_case("gb", Some(4), Some(6); "lowercase GB")]
+    #[test_case("NL", Some(4), None; "NL")]
+    #[test_case("ZZ", None, None; "unknown")]
+    fn identifier_lengths(country_code: &str, bank: Option<usize>, branch: Option<usize>) {
+        assert_eq!(bank_id_len(country_code), bank);
+        assert_eq!(branch_id_len(country_code), branch);
+    }
+
+    #[test_case("DE", Some(18); "DE")]
+    #[test_case("gb", Some(18); "lowercase GB")]
+    #[test_case("NO", Some(11); "NO")]
+    #[test_case("LC", Some(28); "LC")]
+    #[test_case("ZZ", None; "unknown")]
+    fn bban_length(country_code: &str, expected: Option<usize>) {
+        assert_eq!(crate::bban_length(country_code), expected);
+        assert_eq!(
+            crate::lookup_country(country_code).map(|country| country.length - 4),
+            expected
+        );
+    }
+
+    #[test]
+    fn bban_length_examples() {
+        for (country_code, example) in all_examples() {
+            let iban = Iban::parse(example).unwrap();
+            assert_eq!(
+                crate::bban_length(country_code),
+                Some(iban.bban().len()),
+                "{country_code}"
+            );
+        }
+    }
+
+    #[test_case("IQ", Some(true), Some(false); "IQ")]
+    #[test_case("AA", Some(false), Some(false); "AA")]
+    #[test_case("DE", Some(false), Some(false); "DE")]
+    #[test_case("be", Some(false), Some(true); "lowercase BE")]
+    #[test_case("LV", Some(false), Some(false); "LV")]
+    #[test_case("LT", Some(false), Some(false); "LT")]
+    #[test_case("ZZ", None, None; "unknown")]
+    #[test_case("DEU", None, None; "too long")]
+    fn country_supports(country_code: &str, branch: Option<bool>, checksum: Option<bool>) {
+        assert_eq!(country_supports_branch(country_code), branch);
+        assert_eq!(country_supports_checksum(country_code), checksum);
+    }
+
+    #[test_case("IQ98NBIQ850123456789012", Some("NBIQ850"); "IQ")]
+    #[test_case("GB29NWBK60161331926819", Some("NWBK601613"); "GB")]
+    #[test_case("DE89370400440532013000", None; "DE")]
+    fn clearing_identifier(original: &str, expected: Option<&str>) {
+        let iban = Iban::parse(original).expect("iban is valid");
+        assert_eq!(iban.bban().clearing_identifier(), expected);
+    }
+
+    #[test_case("DE89370400440532013000"; "DE")]
+    #[test_case("GB82WEST12345698765432"; "GB")]
+    #[test_case("QA58DOHB00001234567890ABCDEFG"; "QA")]
+    #[test_case("QA58DOHB00001234567890abcdefg"; "QA lowercase account")]
+    fn parse_strict(input: &str) {
+        let iban = Iban::parse_strict(input).expect("iban is valid");
+        assert_eq!(iban.as_str(), input);
+    }
+
+    #[test_case("de89370400440532013000", ParseError::CountryCode; "lowercase country code")]
+    #[test_case("dE89370400440532013000", ParseError::CountryCode; "mixed case country code")]
+    #[test_case("GB82west12345698765432", ParseError::InvalidBban { position: 4, expected: CharacterType::A }; "lowercase bank")]
+    #[test_case("DE89 3704 0044 0532 0130 00", ParseError::InvalidCharacter; "spaced")]
+    #[test_case(" DE89370400440532013000", ParseError::CountryCode; "leading whitespace")]
+    fn parse_strict_error(input: &str, expected_err: ParseError) {
+        assert!(Iban::parse(input).is_ok());
+        assert_eq!(Iban::parse_strict(input), Err(expected_err));
+    }
+
+    #[test_case("DE89370400440532013000", "DE89370400440532013000"; "valid")]
+    #[test_case("DE89 3704 OO44 O532 O13O OO", "DE89370400440532013000"; "O for 0 in numeric BBAN")]
+    #[test_case("8E68 5390 0754 7034", "BE68539007547034"; "misread country code")]
+    #[test_case("DEB9370400440532013000", "DE89370400440532013000"; "misread check digits")]
+    #[test_case("GB82WE5T12345698765432", "GB82WEST12345698765432"; "5 for S in bank")]
+    #[test_case("QA58DOHBO0001234567890ABCDEFG", "QA58DOHB00001234567890ABCDEFG"; "O for 0 in account")]
+    #[test_case("FR14 2OO4 1010 0505 0001 3m02 606", "FR1420041010050500013m02606"; "lowercase in alphanumeric segment")]
+    #[test_case("qa58dohbo0001234567890abcdefg", "QA58DOHB00001234567890abcdefg"; "lowercase O for 0 in account")]
+    fn parse_ocr(input: &str, expected: &str) {
+        let iban = crate::parse_ocr(input).expect("iban is recovered");
+        assert_eq!(iban.as_str(), expected);
+        assert_eq!(Iban::parse(expected), Ok(iban));
+    }
+
+    #[test_case("QA59DOHBOIS8B0154OIS58B01OISX", ParseError::Ambiguous; "ambiguous")]
+    #[test_case("DE88370400440532013000", ParseError::WrongChecksum; "wrong checksum")]
+    #[test_case("DE89 3704 0044 0532 0130", ParseError::TooShort { expected: 22 }; "too short")]
+    #[test_case("ZZ89370400440532013000", ParseError::UnknownCountry; "unknown country")]
+    fn parse_ocr_error(input: &str, expected_err: ParseError) {
+        assert_eq!(crate::parse_ocr(input), Err(expected_err));
+    }
+
+    #[test]
+    fn parse_long_input() {
+        let input = format!("DE89370400440532013000{}", "0".repeat(1_000_000));
+        assert_eq!(
+            Iban::parse(&input),
+            Err(ParseError::TooLong { expected: 22 })
+        );
+
+        // The characters after the expected length are never consumed, so even an endless input
+        // is rejected.
+        let endless = b"DE89370400440532013000"
+            .iter()
+            .copied()
+            .chain(core::iter::repeat(b'0'));
+        assert_eq!(
+            Iban::parse_bytes(endless, crate::Case::Preserve),
+            Err(ParseError::TooLong { expected: 22 })
+        );
+    }
+
+    #[test_case("DE893704004405320130A0", 20, CharacterType::N; "letter for digit")]
+    #[test_case("GB82W3ST12345698765432", 5, CharacterType::A; "digit for letter")]
+    #[test_case("de89 3704 0044 0532 0130 0a", 21, CharacterType::N; "spaced lowercase")]
+    fn parse_invalid_bban_position(input: &str, position: usize, expected: CharacterType) {
+        assert_eq!(
+            Iban::parse(input),
+            Err(ParseError::InvalidBban { position, expected })
+        );
+    }
+
+    #[test_case(
+        ParseError::CountryCode,
+        "invalid country code (expected two ASCII letters)"
+    )]
+    #[test_case(
+        ParseError::CheckDigit,
+        "invalid check digits (expected two ASCII digits)"
+    )]
+    #[test_case(
+        ParseError::InvalidCharacter,
+        "invalid character (expected ASCII letters and digits)"
+    )]
+    #[test_case(ParseError::UnknownCountry, "unknown country code")]
+    #[test_case(
+        ParseError::UnsupportedFormat,
+        "unsupported format (the country is known, but its format cannot be validated)"
+    )]
+    #[test_case(ParseError::TooShort { expected: 22 }, "too short (expected length 22)")]
+    #[test_case(ParseError::TooLong { expected: 22 }, "too long (expected length 22)")]
+    #[test_case(
+        ParseError::InvalidBban { position: 20, expected: CharacterType::N },
+        "invalid bban (expected a digit at position 20)"
+    )]
+    #[test_case(ParseError::Ambiguous, "ambiguous (several corrections are valid)")]
+    #[test_case(
+        ParseError::Exhausted,
+        "exhausted (not enough distinct IBANs could be generated)"
+    )]
+    #[test_case(
+        ParseError::WrongChecksum,
+        "checksum validation failed (check digits are wrong)"
+    )]
+    fn parse_error_display(err: ParseError, expected: &str) {
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_error_into_boxed_error() {
+        fn parse(s: &str) -> Result<Iban, Box<dyn std::error::Error>> {
+            Ok(Iban::parse(s)?)
+        }
+
+        let err = parse("DE8937040044053201300").unwrap_err();
+        assert_eq!(err.to_string(), "too short (expected length 22)");
+        assert!(err.source().is_none());
+        assert_eq!(
+            err.downcast_ref::<ParseError>(),
+            Some(&ParseError::TooShort { expected: 22 })
+        );
+    }
+
+    #[test_case("DE89370400440532013000", Membership::EuMember; "EU")]
+    #[test_case("NO9386011117947", Membership::EftaMember; "EEA non-EU")]
+    #[test_case("CH9300762011623852957", Membership::EftaMember; "EFTA non-EEA")]
+    #[test_case("GB82WEST12345698765432", Membership::OtherMember; "other")]
+    #[test_case("BR1800360305000010009795493C1", Membership::NonMember; "non-European")]
+    fn membership(original: &str, expected: Membership) {
+        let iban = Iban::parse(original).expect("iban is valid");
+        assert_eq!(iban.membership(), expected);
+        assert_eq!(iban.is_eu(), expected == Membership::EuMember);
+    }
+
+    #[test]
+    fn country_info() {
+        let iban = Iban::parse("GB82WEST12345698765432").expect("iban is valid");
+        let info = iban.country_info();
+
+        assert_eq!(info.length, 22);
+        assert_eq!(
+            info.format,
+            [
+                (1, CharacterType::S(b'G')),
+                (1, CharacterType::S(b'B')),
+                (2, CharacterType::N),
+                (4, CharacterType::A),
+                (6, CharacterType::N),
+                (8, CharacterType::N),
+            ]
+        );
+        assert_eq!(info.bank_identifier, Some((0, 4)));
+        assert_eq!(info.branch_identifier, Some((4, 10)));
+        assert_eq!(info.checksum, None);
+    }
+
+    #[test_case("ZZ12ABC123"; "unknown country")]
+    #[test_case("zz12 abc1 23"; "spaced lowercase")]
+    #[test_case("DE89370400440532013000"; "valid")]
+    fn parse_checksum_only(input: &str) {
+        let iban = Iban::parse_checksum_only(input).expect("checksum is valid");
+        let expected = input.replace(' ', "").to_ascii_uppercase();
+        assert_eq!(iban.as_str(), expected);
+        assert_eq!(iban.country_code(), &expected[..2]);
+        assert_eq!(iban.check_digits(), &expected[2..4]);
+        assert_eq!(iban.bban(), &expected[4..]);
+        assert_eq!(Iban::parse_checksum_only(&format!("{iban}")), Ok(iban));
+    }
+
+    #[test_case("qa58dohb00001234567890abcdefg"; "QA lowercase")]
+    #[test_case("BL68 2004 1010 0505 0001 3m02 606"; "BL")]
+    #[test_case("gb82 west 1234 5698 7654 32"; "GB alphabetic segment")]
+    fn parse_checksum_only_case(input: &str) {
+        let iban = Iban::parse_checksum_only(input).expect("checksum is valid");
+        assert_eq!(iban.to_iban(), Iban::parse(input));
+        assert_eq!(iban.as_str(), Iban::parse(input).unwrap().as_str());
+    }
+
+    #[test_case("Z112ABC123", ParseError::CountryCode; "country code")]
+    #[test_case("ZZA2ABC123", ParseError::CheckDigit; "check digits")]
+    #[test_case("ZZ12ABC-123", ParseError::InvalidCharacter; "invalid character")]
+    #[test_case("ZZ13ABC123", ParseError::WrongChecksum; "wrong checksum")]
+    #[test_case("ZZ1234567890123456789012345678901234", ParseError::TooLong { expected: 34 }; "too long")]
+    fn parse_checksum_only_error(input: &str, expected_err: ParseError) {
+        assert_eq!(Iban::parse_checksum_only(input), Err(expected_err));
+    }
+
+    #[test_case("ZZ12ABC123", ParseError::UnknownCountry; "unknown country")]
+    #[test_case("DE22370400440532", ParseError::TooShort { expected: 22 }; "too short")]
+    #[test_case("DE6537040044053201300012", ParseError::TooLong { expected: 22 }; "too long")]
+    #[test_case("HR7012100050511", ParseError::TooShort { expected: 21 }; "HR too short")]
+    fn parse_checksum_only_unexpected_format(input: &str, expected_err: ParseError) {
+        let iban = Iban::parse_checksum_only(input).expect("checksum is valid");
+        assert_eq!(iban.to_iban(), Err(expected_err));
+        assert_eq!(Iban::parse(input), Err(expected_err));
+    }
+
+    #[test]
+    fn checksum_offsets_at_end_of_bban() {
+        // CF's registry checksum offsets end one past the BBAN, and are clamped to its end.
+        let iban = Iban::parse("CF4220001000010120069700160").unwrap();
+        assert_eq!(iban.bban().checksum(), Some("60"));
+    }
+
+    #[test]
+    fn eq_array_string() {
+        let iban = Iban::parse("DE89 3704 0044 0532 0130 00").expect("iban is valid");
+        let buffer = iban.into_inner();
+        assert_eq!(iban, buffer);
+        assert_eq!(buffer, iban);
+
+        let other = Iban::parse("GB82WEST12345698765432").expect("iban is valid");
+        assert_ne!(other, buffer);
+        assert_ne!(buffer, other);
+
+        // Only the electronic format is equal.
+        let spaced = arrayvec::ArrayString::<34>::from("DE89 3704 0044 0532 0130 00").unwrap();
+        assert_ne!(iban, spaced);
+    }
+
+    #[test_case("DE02370400440532010007", "DE99370400440532010007", true; "check digits differ")]
+    #[test_case("DE89370400440532013000", "DE89370400440532013000", true; "equal")]
+    #[test_case("DE89370400440532013000", "DE02370400440532010007", false; "bban differs")]
+    #[test_case("LI21088100002324013AA", "CH9300762011623852957", false; "country differs")]
+    fn same_account(a: &str, b: &str, expected: bool) {
+        let a = Iban::parse(a).expect("iban is valid");
+        let b = Iban::parse(b).expect("iban is valid");
+
+        assert_eq!(a.same_account(&b), expected);
+        assert_eq!(b.same_account(&a), expected);
+    }
+
+    #[test_case("de", true; "lowercase")]
+    #[test_case("DE", true; "uppercase")]
+    #[test_case("FR", false; "other country")]
+    #[test_case("DEU", false; "alpha-3")]
+    fn matches_country(country_code: &str, expected: bool) {
+        let iban = Iban::parse("DE89370400440532013000").expect("iban is valid");
+        assert_eq!(iban.matches_country(country_code), expected);
+    }
+
+    #[test_case("DE89370400440532013000", true; "EU")]
+    #[test_case("CH9300762011623852957", true; "non-EU")]
+    #[test_case("BR9700360305000010009795493P1", false; "non-SEPA")]
+    fn sepa(original: &str, expected: bool) {
+        let iban = Iban::parse(original).expect("iban is valid");
+        assert_eq!(iban.is_sepa(), expected);
+    }
+
+    // AA is only marked as deprecated in tests.
+    #[test_case("AA110011123Z5678", true; "marked")]
+    #[test_case("XK051212012345678906", false; "not marked")]
+    #[test_case("DE89370400440532013000", false; "DE")]
+    fn deprecated_country(original: &str, expected: bool) {
+        let iban = Iban::parse(original).expect("iban is valid");
+        assert_eq!(iban.is_deprecated_country(), expected);
+    }
+
+    #[test_case("SI56191000000123438", Some(true); "SI")]
+    #[test_case("SI29191000000123439", Some(false); "SI corrupted account")]
+    #[test_case("HR1210010051863000160", Some(true); "HR")]
+    #[test_case("HR7410010041863000160", Some(false); "HR corrupted bank")]
+    #[test_case("HR8210010051863000161", Some(false); "HR corrupted account")]
+    #[test_case("TN5910006035183598478831", Some(true); "TN")]
+    #[test_case("TN3210006035183598478832", Some(false); "TN corrupted key")]
+    #[test_case("TN0810007035183598478831", Some(false); "TN corrupted account")]
+    #[test_case("DK8830011234567892", Some(true); "DK")]
+    #[test_case("DK6130011234567893", Some(false); "DK corrupted account")]
+    #[test_case("DK5000400440116243", None; "DK exempt")]
+    #[test_case("FO7131000001234579", Some(true); "FO")]
+    #[test_case("FO2331000001234570", Some(false); "FO corrupted account")]
+    #[test_case("FO2000400440116243", None; "FO exempt")]
+    #[test_case("GL0339999876543211", Some(true); "GL")]
+    #[test_case("GL7339999876543212", Some(false); "GL corrupted account")]
+    #[test_case("GL2000400440116243", None; "GL exempt")]
+    #[test_case("DZ3512341234123412341234", None; "DZ")]
+    #[test_case("MA64011519000001205000534921", None; "MA")]
+    #[test_case("DE89370400440532013000", None; "DE")]
+    #[test_case("LV80BANK0000435195001", None; "LV")]
+    #[test_case("LT121000011101001000", None; "LT")]
+    fn national_checksum(original: &str, expected: Option<bool>) {
+        let iban = Iban::parse(original).expect("iban is valid");
+        assert_eq!(iban.bban().validate_national_checksum(), expected);
+        if expected.is_some() {
+            assert_eq!(
+                crate::country_supports_national_checksum(iban.country_code()),
+                Some(true)
+            );
+        }
+    }
+
+    // `01` satisfies MOD 97-10 on its own, so only the length guard rejects it.
+    #[test_case("SI", "01"; "SI")]
+    #[test_case("SI", "1910000001234380"; "SI too long")]
+    #[test_case("HR", "1001005186300016"; "HR")]
+    fn national_checksum_wrong_length(country_code: &str, bban: &str) {
+        assert_eq!(crate::national::validate(country_code, bban), None);
+    }
+
+    #[test_case("TN", Some(true); "TN")]
+    #[test_case("dk", Some(true); "DK lowercase")]
+    #[test_case("DZ", Some(false); "DZ")]
+    #[test_case("MA", Some(false); "MA")]
+    #[test_case("XX", None; "unknown")]
+    fn national_checksum_support(country_code: &str, expected: Option<bool>) {
+        assert_eq!(
+            crate::country_supports_national_checksum(country_code),
+            expected
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let old = Iban::parse("DE89370400440532013000").unwrap();
+        let new = Iban::parse("DE84370400440532013090").unwrap();
+        assert_eq!(
+            crate::diff(&old, &new).collect::<Vec<_>>(),
+            [(3, Some('9'), Some('4')), (20, Some('0'), Some('9'))]
+        );
+        assert_eq!(crate::diff(&old, &old).count(), 0);
+    }
+
+    #[test]
+    fn diff_lengths() {
+        let old = Iban::parse("NO9386011117947").unwrap();
+        let new = Iban::parse("BE68539007547034").unwrap();
+        let changes = crate::diff(&old, &new).collect::<Vec<_>>();
+        assert_eq!(changes.last(), Some(&(15, None, Some('4'))));
+        assert_eq!(crate::diff(&new, &old).last(), Some((15, Some('4'), None)));
+    }
+
+    #[test_case("XD89370400440532013000", Ok("DE89370400440532013000"); "alias")]
+    #[test_case("xd89 3704 0044 0532 0130 00", Ok("DE89370400440532013000"); "alias lowercase")]
+    #[test_case("DE89370400440532013000", Ok("DE89370400440532013000"); "canonical")]
+    #[test_case("GB82WEST12345698765432", Ok("GB82WEST12345698765432"); "not aliased")]
+    #[test_case("XD82WEST12345698765432", Err(ParseError::InvalidBban { position: 4, expected: CharacterType::N }); "wrong country")]
+    #[test_case("XE89370400440532013000", Err(ParseError::UnknownCountry); "unknown alias")]
+    #[test_case("X", Err(ParseError::CountryCode); "short")]
+    fn parse_with_aliases(original: &str, expected: Result<&str, ParseError>) {
+        let aliases = [("XD", "DE"), ("UK", "GB")];
+        assert_eq!(
+            Iban::parse_with_aliases(original, &aliases)
+                .as_ref()
+                .map(Iban::as_str)
+                .map_err(|&err| err),
+            expected
+        );
+    }
+
+    #[test_case("DE89370400440532013000", "1234567890", Ok("DE14370400441234567890"); "DE")]
+    #[test_case("GB82WEST12345698765432", "11223344", Ok("GB16WEST12345611223344"); "GB")]
+    #[test_case("DE89370400440532013000", "123", Err(ParseError::InvalidBban { position: 15, expected: CharacterType::N }); "short")]
+    #[test_case("DE89370400440532013000", "12345678901", Err(ParseError::TooLong { expected: 22 }); "long")]
+    #[test_case("DE89370400440532013000", "12345678X0", Err(ParseError::InvalidBban { position: 20, expected: CharacterType::N }); "invalid")]
+    fn with_account(original: &str, account: &str, expected: Result<&str, ParseError>) {
+        let iban = Iban::parse(original).unwrap();
+        let result = iban.with_account(account);
+        assert_eq!(
+            result.as_ref().map(Iban::as_str).map_err(|&err| err),
+            expected
+        );
+
+        if let Ok(other) = result {
+            assert_eq!(
+                other.bban().bank_identifier(),
+                iban.bban().bank_identifier()
+            );
+            assert_eq!(
+                other.bban().branch_identifier(),
+                iban.bban().branch_identifier()
+            );
+            assert_ne!(other, iban);
+            assert_eq!(Iban::parse(other.as_str()), Ok(other));
+        }
+    }
+
+    #[test_case("DE00370400440532013000", Ok("DE00370400440532013000"); "wrong check digits")]
+    #[test_case("DE89370400440532013000", Ok("DE89370400440532013000"); "valid")]
+    #[test_case("DE0037040044053201300", Err(ParseError::TooShort { expected: 22 }); "too short")]
+    #[test_case("DEXX370400440532013000", Err(ParseError::CheckDigit); "check digits")]
+    fn parse_with_checksum(original: &str, expected: Result<&str, ParseError>) {
+        assert_eq!(
+            Iban::parse_with_checksum(original, &|_| true)
+                .as_ref()
+                .map(Iban::as_str)
+                .map_err(|&err| err),
+            expected
+        );
+    }
+
+    #[test_case("DE89370400440532013000", 0, true; "standard")]
+    #[test_case("DE88370400440532013000", 0, true; "remainder 0")]
+    #[test_case("DE87370400440532013000", 0, false; "remainder 96")]
+    #[test_case("DE87370400440532013000", 96, true; "custom remainder")]
+    #[test_case("DE88370400440532013000", 1, false; "standard only")]
+    fn parse_with_remainder(original: &str, remainder: u32, valid: bool) {
+        let expected = if valid {
+            Ok(original)
+        } else {
+            Err(ParseError::WrongChecksum)
+        };
+        assert_eq!(
+            Iban::parse_with_remainder(original, remainder)
+                .as_ref()
+                .map(Iban::as_str)
+                .map_err(|&err| err),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_with_remainder_is_not_standard() {
+        assert!(Iban::parse_with_remainder("DE88370400440532013000", 0).is_ok());
+        assert_eq!(
+            Iban::parse("DE88370400440532013000"),
+            Err(ParseError::WrongChecksum)
+        );
+    }
+
+    #[test]
+    fn parse_with_checksum_rejected() {
+        let standard = |iban: &[u8]| crate::calculate_checksum(iban) == 1;
+        assert!(Iban::parse_with_checksum("DE89370400440532013000", &standard).is_ok());
+        assert_eq!(
+            Iban::parse_with_checksum("DE89370400440532013000", &|_| false),
+            Err(ParseError::WrongChecksum)
+        );
+    }
+
+    #[test_case("DE", Some((0, 8)), true; "DE")]
+    #[test_case("AT", Some((0, 5)), true; "AT")]
+    #[test_case("FR", Some((0, 5)), true; "FR")]
+    #[test_case("GB", Some((0, 4)), true; "GB")]
+    #[test_case("AX", Some((0, 6)), false; "AX")]
+    fn first_bban_segment(
+        country_code: &str,
+        expected: Option<(usize, usize)>,
+        matches_explicit: bool,
+    ) {
+        let country = crate::lookup_country(country_code).unwrap();
+        let heuristic = crate::first_bban_segment(country.format);
+        assert_eq!(heuristic, expected);
+        assert_eq!(heuristic == country.bank_identifier, matches_explicit);
+    }
+
+    #[test_case("DE89370400440532013000", Some("37040044"); "explicit")]
+    #[test_case("AX2112345600000785", Some("123"); "explicit shorter than segment")]
+    #[test_case("BJ83A12312341234123412341234", Some("A"); "BJ")]
+    #[test_case("AO44123412341234123412341", None; "AO single segment")]
+    #[test_case("DZ3512341234123412341234", None; "DZ single segment")]
+    fn bank_identifier_heuristic(original: &str, expected: Option<&str>) {
+        let iban = Iban::parse(original).unwrap();
+        assert_eq!(iban.bban().bank_identifier_heuristic(), expected);
+        if iban.bban().bank_identifier().is_some() {
+            assert_eq!(iban.bban().bank_identifier(), expected);
+        }
+    }
+
+    #[test_case("DE89370400440532013000", Some("DE62370400440532013001"); "DE")]
+    #[test_case("GB82WEST12345698765432", Some("GB55WEST12345698765433"); "GB")]
+    #[test_case("DE33370400449999999999", None; "overflow")]
+    #[test_case("FR1420041010050500013M02606", None; "alphanumeric account")]
+    fn increment_account(original: &str, expected: Option<&str>) {
+        let iban = Iban::parse(original).unwrap();
+        let next = iban.increment_account();
+        assert_eq!(next.as_ref().map(Iban::as_str), expected);
+
+        if let Some(next) = next {
+            assert_ne!(next, iban);
+            assert_eq!(Iban::parse(next.as_str()), Ok(next));
+            assert_eq!(next.bban().bank_identifier(), iban.bban().bank_identifier());
+            assert_eq!(
+                next.bban().branch_identifier(),
+                iban.bban().branch_identifier()
+            );
+        }
+    }
+
+    #[test]
+    fn countries_with_national_check() {
+        let countries = crate::countries_with_national_check().collect::<Vec<_>>();
+        for country_code in ["DK", "HR", "SI", "TN"] {
+            assert!(countries.contains(&country_code), "{country_code}");
+        }
+        assert!(!countries.contains(&"DE"));
+
+        for country_code in countries {
+            assert_eq!(
+                crate::country_supports_national_checksum(country_code),
+                Some(true)
+            );
+        }
+    }
+
+    #[test_case("DE", true; "DE")]
+    #[test_case("de", true; "DE lowercase")]
+    #[test_case("XK", false; "XK registry only")]
+    #[test_case("ZZ", false; "unknown")]
+    fn iso3166(country_code: &str, expected: bool) {
+        assert_eq!(crate::is_iso3166(country_code), expected);
+    }
+
+    #[test]
+    fn registry_only_country() {
+        let iban = Iban::parse("XK05 1212 0123 4567 8906").expect("XK should be supported");
+        assert_eq!(iban.country_code(), "XK");
+        assert!(!crate::is_iso3166(iban.country_code()));
+    }
+
     #[cfg(feature = "rand")]
     #[test]
     fn random_iban() {
@@ -902,9 +4626,138 @@ mod tests {
         assert_eq!(bban.checksum(), None);
 
         assert_eq!(Iban::rand("Z1", &mut rng), Err(ParseError::CountryCode));
+        assert_eq!(Iban::rand("G", &mut rng), Err(ParseError::CountryCode));
+        assert_eq!(Iban::rand("GBR", &mut rng), Err(ParseError::UnknownCountry));
         assert_eq!(Iban::rand("ZZ", &mut rng), Err(ParseError::UnknownCountry));
     }
 
+    #[cfg(all(feature = "rand", feature = "alloc"))]
+    #[test]
+    fn random_batch() {
+        use rand::SeedableRng;
+        use std::collections::HashSet;
+
+        let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
+        let ibans = Iban::rand_batch("DE", 100, &mut rng).expect("generates random (seeded) ibans");
+
+        assert_eq!(ibans.len(), 100);
+        assert_eq!(ibans.iter().collect::<HashSet<_>>().len(), 100);
+        for iban in &ibans {
+            assert_eq!(Iban::parse(iban), Ok(*iban));
+        }
+
+        assert_eq!(
+            Iban::rand_batch("ZZ", 1, &mut rng),
+            Err(ParseError::UnknownCountry)
+        );
+        assert_eq!(Iban::rand_batch("DE", 0, &mut rng), Ok(Vec::new()));
+
+        let mut rng = rand::rngs::mock::StepRng::new(0, 0);
+        assert_eq!(
+            Iban::rand_batch("DE", 1, &mut rng).map(|ibans| ibans.len()),
+            Ok(1)
+        );
+        assert_eq!(
+            Iban::rand_batch("DE", 2, &mut rng),
+            Err(ParseError::Exhausted)
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test_case("DE", "DE39430870634724101729"; "DE")]
+    #[test_case("NO", "NO8543087063472"; "NO")]
+    #[test_case("QA", "QA92KIBVj4eNTkCTA6BkFzCEin8hn"; "QA")]
+    fn random_snapshot(country_code: &str, expected: &str) {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
+        let iban = Iban::rand(country_code, &mut rng).expect("generates random (seeded) iban");
+
+        assert_eq!(iban.as_str(), expected);
+        assert_eq!(Iban::parse(&iban), Ok(iban));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test_case("GB", "WEST", Some("123456"); "GB")]
+    #[test_case("gb", "west", Some("123456"); "GB lowercase")]
+    #[test_case("FR", "20041", Some("01005"); "FR")]
+    #[test_case("DE", "37040044", None; "DE without branch")]
+    #[test_case("GB", "NWBK", None; "GB random branch")]
+    fn random_account(country_code: &str, bank_id: &str, branch_id: Option<&str>) {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
+
+        let mut residuals = std::collections::BTreeSet::new();
+        for _ in 0..16 {
+            let iban = Iban::rand_account(country_code, bank_id, branch_id, &mut rng)
+                .expect("generates iban");
+            assert_eq!(Iban::parse(&iban), Ok(iban));
+
+            let bban = iban.bban();
+            assert_eq!(bban.bank_identifier(), Some(&*bank_id.to_ascii_uppercase()));
+            if branch_id.is_some() {
+                assert_eq!(bban.branch_identifier(), branch_id);
+            }
+            residuals.insert(bban.residual().to_owned());
+        }
+        assert!(residuals.len() > 1, "accounts should differ");
+    }
+
+    #[cfg(feature = "rand")]
+    #[test_case("GB", "WES", Some("123456"), ParseError::InvalidBban { position: 7, expected: CharacterType::A }; "bank too short")]
+    #[test_case("GB", "WEST1", Some("123456"), ParseError::InvalidBban { position: 8, expected: CharacterType::N }; "bank too long")]
+    #[test_case("GB", "WE5T", Some("123456"), ParseError::InvalidBban { position: 6, expected: CharacterType::A }; "bank invalid")]
+    #[test_case("GB", "WEST", Some("12-456"), ParseError::InvalidCharacter; "branch invalid character")]
+    #[test_case("IT", "X0300", Some("123456"), ParseError::InvalidBban { position: 15, expected: CharacterType::C }; "branch too long")]
+    #[test_case("DE", "37040044", Some("1"), ParseError::UnsupportedFormat; "no branch")]
+    #[test_case("ZZ", "1234", None, ParseError::UnknownCountry; "unknown country")]
+    fn random_account_error(
+        country_code: &str,
+        bank_id: &str,
+        branch_id: Option<&str>,
+        expected_err: ParseError,
+    ) {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
+
+        assert_eq!(
+            Iban::rand_account(country_code, bank_id, branch_id, &mut rng),
+            Err(expected_err)
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test_case("HR"; "HR")]
+    #[test_case("SI"; "SI")]
+    #[test_case("TN"; "TN")]
+    fn random_plausible(country_code: &str) {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
+
+        for _ in 0..16 {
+            let iban = Iban::rand_plausible(country_code, &mut rng).expect("generates iban");
+            assert_eq!(Iban::parse(&iban), Ok(iban));
+            assert_eq!(iban.bban().validate_national_checksum(), Some(true));
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_plausible_unsupported() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
+        let mut plausible_rng = rand::rngs::StdRng::from_seed([0; 32]);
+
+        assert_eq!(
+            Iban::rand_plausible("DE", &mut plausible_rng),
+            Iban::rand("DE", &mut rng)
+        );
+        assert_eq!(
+            Iban::rand_plausible("ZZ", &mut plausible_rng),
+            Err(ParseError::UnknownCountry)
+        );
+    }
+
     #[cfg(feature = "rand")]
     #[test]
     fn random_all_countries() {
@@ -918,6 +4771,116 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_display_round_trip() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
+
+        for country in crate::COUNTRIES.keys() {
+            for _ in 0..16 {
+                let iban = Iban::rand(country, &mut rng).expect("generates random (seeded) iban");
+                let spaced = iban.to_string();
+
+                assert_eq!(
+                    Iban::parse(&spaced),
+                    Ok(iban),
+                    "{spaced:?} should round-trip"
+                );
+            }
+        }
+    }
+
+    /// Guards against the build script silently generating an incomplete country table.
+    #[test]
+    fn supported_countries() {
+        assert!(crate::supported_countries().count() >= 80);
+        assert!(crate::supported_countries()
+            .all(|country_code| crate::lookup_country(country_code).is_some()));
+    }
+
+    /// Every example in the registry must be accepted by the rules generated from the registry.
+    #[test]
+    fn examples() {
+        let mut count = 0;
+        let mut failures = Vec::new();
+        for (country_code, example) in all_examples() {
+            count += 1;
+
+            let iban = match Iban::parse(example) {
+                Ok(iban) => iban,
+                Err(err) => {
+                    failures.push(format!(
+                        "{country_code}: {example:?} failed to parse: {err}"
+                    ));
+                    continue;
+                }
+            };
+            if iban.country_code() != country_code {
+                failures.push(format!(
+                    "{country_code}: {example:?} has country code {:?}",
+                    iban.country_code()
+                ));
+            }
+            let expected_length = crate::COUNTRIES[country_code].length;
+            if iban.len() != expected_length {
+                failures.push(format!(
+                    "{country_code}: {example:?} has length {}, expected {expected_length}",
+                    iban.len()
+                ));
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "{} registry example(s) rejected:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+        assert_eq!(count, crate::COUNTRIES.len());
+    }
+
+    /// Every segment defined by the registry must be within the BBAN of the country's example.
+    #[test]
+    fn example_accessors() {
+        let mut failures = Vec::new();
+        for (country_code, example) in all_examples() {
+            let country = &crate::COUNTRIES[country_code];
+            let bban = Iban::parse(example).expect("example is valid").bban();
+            let segments = [
+                ("bank", country.bank_identifier, bban.bank_identifier()),
+                (
+                    "branch",
+                    country.branch_identifier,
+                    bban.branch_identifier(),
+                ),
+                ("checksum", country.checksum, bban.checksum()),
+            ];
+            for (name, offset, segment) in segments {
+                let expected = offset.map(|(start, end)| end - start);
+                if segment.map(str::len) != expected {
+                    failures.push(format!("{country_code}: {name} {segment:?} for {offset:?}"));
+                }
+            }
+
+            let _ = (
+                bban.bank_identifier_heuristic(),
+                bban.bank_identifier_trimmed(),
+                bban.clearing_identifier(),
+                bban.residual(),
+                bban.validate_national_checksum(),
+                bban.as_u128(),
+            );
+        }
+
+        assert!(
+            failures.is_empty(),
+            "{} segment(s) outside the BBAN:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+
     #[test]
     fn character_types() {
         assert!(!CharacterType::N.contains(b'A'));
@@ -0,0 +1,103 @@
+/// Validates the national check digits of a BBAN.
+///
+/// Returns `None` if no national check algorithm is implemented for the country, or if the BBAN
+/// does not have the length of the country's format.
+pub fn validate(country_code: &str, bban: &str) -> Option<bool> {
+    match country_code {
+        // Bank identifier (7 digits) and account number (10 digits) each end in an ISO 7064 MOD 11,10 check digit.
+        "HR" if bban.len() == 17 => Some(mod11_10(&bban[0..7]) && mod11_10(&bban[7..17])),
+        // The trailing two digits are ISO 7064 MOD 97-10 check digits over the whole BBAN.
+        "SI" if bban.len() == 15 => Some(mod97_10(bban)),
+        // The trailing two digits are a RIB key, which makes the whole BBAN divisible by 97.
+        "TN" => Some(mod97(bban) == Some(0)),
+        // Registration number (4 digits) and account number (10 digits), shared by the Faroe
+        // Islands and Greenland.
+        "DK" | "FO" | "GL" if bban.len() == 14 => danish(&bban[0..4], &bban[4..14]),
+        _ => None,
+    }
+}
+
+/// Ranges of Danish registration numbers whose accounts end in a MOD 11 check digit.
+///
+/// Other banks (including those of the registry examples) use bank-specific check digits or none
+/// at all, so their accounts are exempt from the check.
+const DANISH_MOD11_REGISTRATION_NUMBERS: &[(u32, u32)] = &[(3000, 3999)];
+
+/// Validates a Danish account number, if its bank uses the MOD 11 check digit.
+///
+/// The digits of the account number are weighted with 4, 3, 2, 7, 6, 5, 4, 3, 2, 1, and their sum
+/// must be divisible by 11. Returns `None` for accounts that are exempt from the check.
+fn danish(registration_number: &str, account_number: &str) -> Option<bool> {
+    let registration_number = registration_number
+        .bytes()
+        .try_fold(0, |number, byte| Some(number * 10 + digit(byte)?))?;
+    if !DANISH_MOD11_REGISTRATION_NUMBERS
+        .iter()
+        .any(|&(start, end)| (start..=end).contains(&registration_number))
+    {
+        return None;
+    }
+
+    Some(weighted_mod11(
+        account_number,
+        &[4, 3, 2, 7, 6, 5, 4, 3, 2, 1],
+    ))
+}
+
+/// The countries for which a national check algorithm is implemented.
+///
+/// This must be kept in sync with the countries handled by [`validate`].
+pub const SUPPORTED: &[&str] = &["DK", "FO", "GL", "HR", "SI", "TN"];
+
+/// Returns true if a national check algorithm is implemented for the (case-insensitive) country code.
+pub fn is_supported(country_code: &str) -> bool {
+    SUPPORTED
+        .iter()
+        .any(|supported| supported.eq_ignore_ascii_case(country_code))
+}
+
+/// Converts an ASCII digit to its numeric value.
+#[inline]
+fn digit(byte: u8) -> Option<u32> {
+    if byte.is_ascii_digit() {
+        Some(u32::from(byte - b'0'))
+    } else {
+        None
+    }
+}
+
+/// Returns the remainder of `digits` divided by 97, or `None` if it contains non-digits.
+pub fn mod97(digits: &str) -> Option<u32> {
+    digits.bytes().try_fold(0u32, |remainder, byte| {
+        digit(byte).map(|value| (remainder * 10 + value) % 97)
+    })
+}
+
+/// Returns true if `digits` (including its trailing check digits) satisfies ISO 7064 MOD 97-10.
+pub fn mod97_10(digits: &str) -> bool {
+    mod97(digits) == Some(1)
+}
+
+/// Returns true if the sum of `digits` multiplied by their `weights` is divisible by 11.
+pub fn weighted_mod11(digits: &str, weights: &[u32]) -> bool {
+    digits.len() == weights.len()
+        && digits
+            .bytes()
+            .zip(weights)
+            .try_fold(0, |sum, (byte, weight)| Some(sum + digit(byte)? * weight))
+            .map_or(false, |sum| sum % 11 == 0)
+}
+
+/// Returns true if `digits` (including its trailing check digit) satisfies ISO 7064 MOD 11,10.
+pub fn mod11_10(digits: &str) -> bool {
+    digits
+        .bytes()
+        .try_fold((10u32, 0u32), |(product, _), byte| {
+            let sum = match (digit(byte)? + product) % 10 {
+                0 => 10,
+                sum => sum,
+            };
+            Some(((sum * 2) % 11, sum))
+        })
+        .map_or(false, |(_, sum)| sum == 1)
+}
@@ -0,0 +1,136 @@
+//! Recovery of IBANs from text with OCR confusions.
+
+use arrayvec::ArrayVec;
+
+use crate::{
+    bban_character_types, country_format, Case, CharacterType, Iban, ParseError, COUNTRIES,
+    IBAN_MAX_LENGTH,
+};
+
+/// Pairs of characters that are commonly confused by OCR, as `(letter, digit)`.
+const CONFUSABLE: &[(u8, u8)] = &[(b'O', b'0'), (b'I', b'1'), (b'S', b'5'), (b'B', b'8')];
+
+/// Returns the digit that is commonly confused with the letter `ch`.
+fn to_digit(ch: u8) -> Option<u8> {
+    CONFUSABLE
+        .iter()
+        .find(|&&(letter, _)| letter == ch)
+        .map(|&(_, digit)| digit)
+}
+
+/// Returns the letter that is commonly confused with the digit `ch`.
+fn to_letter(ch: u8) -> Option<u8> {
+    CONFUSABLE
+        .iter()
+        .find(|&&(_, digit)| digit == ch)
+        .map(|&(letter, _)| letter)
+}
+
+/// Returns the character that is commonly confused with `ch`.
+fn confused(ch: u8) -> Option<u8> {
+    to_digit(ch).or_else(|| to_letter(ch))
+}
+
+/// Parse a string as an Iban, recovering from characters that are commonly confused by OCR.
+///
+/// If the input is not a valid IBAN, the confusable pairs `O`/`0`, `I`/`1`, `S`/`5` and `B`/`8`
+/// are substituted where needed to find a valid IBAN:
+/// * Where the format only allows digits (or only letters), confusable characters are
+///   replaced by the digit (or letter) they are confused with.
+/// * Where the format allows both, up to two confusable characters at a time are substituted,
+///   and every combination is tried.
+///
+/// Case is handled as by [`Iban::parse`], so lowercase characters are kept in segments that allow
+/// them. Lowercase letters are considered confusable as well, such as `o` for `0`.
+///
+/// The corrected IBAN is returned if it is the only candidate with a valid checksum. Note that
+/// this weakens the protection of the check digits against typos, so the result should be
+/// confirmed by the user.
+///
+/// ```
+/// // `O` instead of `0` in the numeric German BBAN.
+/// let iban = iban::parse_ocr("DE89 3704 OO44 O532 O13O OO")?;
+/// assert_eq!(iban.as_str(), "DE89370400440532013000");
+/// # Ok::<(), iban::ParseError>(())
+/// ```
+///
+/// # Errors
+/// This function returns `ParseError::Ambiguous` if several candidates are valid IBANs,
+/// and otherwise the same errors as [`Iban::parse`] for the original input.
+pub fn parse_ocr(s: &str) -> Result<Iban, ParseError> {
+    let error = match Iban::parse(s) {
+        Ok(iban) => return Ok(iban),
+        Err(error) => error,
+    };
+
+    let mut characters = ArrayVec::<u8, IBAN_MAX_LENGTH>::new();
+    for ch in s.bytes().filter(|byte| !byte.is_ascii_whitespace()) {
+        characters.try_push(ch).map_err(|_| error)?;
+    }
+    if characters.len() < 4 {
+        return Err(error);
+    }
+    // The BBAN is normalized once its format is known, so that lowercase characters are kept
+    // where `Iban::parse` keeps them.
+    characters[..4].make_ascii_uppercase();
+
+    // The country code only allows letters, and the check digits only allow digits.
+    for ch in &mut characters[..2] {
+        *ch = to_letter(*ch).unwrap_or(*ch);
+    }
+    for ch in &mut characters[2..4] {
+        *ch = to_digit(*ch).unwrap_or(*ch);
+    }
+
+    let country = core::str::from_utf8(&characters[..2])
+        .ok()
+        .and_then(|country_code| COUNTRIES.get(country_code))
+        .ok_or(error)?;
+    let format = country_format(country).map_err(|_| error)?;
+    if country.length != characters.len() {
+        return Err(error);
+    }
+
+    // Positions that allow both letters and digits, where either of the pair could be intended.
+    let mut ambiguous = ArrayVec::<usize, IBAN_MAX_LENGTH>::new();
+    for (position, character_type) in bban_character_types(format).enumerate() {
+        let position = position + 4;
+        let ch = &mut characters[position];
+        *ch = Case::Preserve.apply(character_type, *ch);
+        match character_type {
+            CharacterType::N => *ch = to_digit(*ch).unwrap_or(*ch),
+            CharacterType::A => *ch = to_letter(*ch).unwrap_or(*ch),
+            CharacterType::C | CharacterType::I => {
+                if confused(ch.to_ascii_uppercase()).is_some() {
+                    ambiguous.push(position);
+                }
+            }
+            CharacterType::S(_) => {}
+        }
+    }
+
+    let mut found = None;
+    let mut try_candidate = |substituted: &[usize]| {
+        let mut candidate = characters.clone();
+        for &position in substituted {
+            let ch = &mut candidate[position];
+            *ch = confused(ch.to_ascii_uppercase()).unwrap_or(*ch);
+        }
+
+        match Iban::parse_bytes(candidate.iter().copied(), Case::Preserve) {
+            Ok(iban) if found.replace(iban).is_some() => Err(ParseError::Ambiguous),
+            _ => Ok(()),
+        }
+    };
+
+    // Try every combination of at most two substitutions at the ambiguous positions.
+    try_candidate(&[])?;
+    for (index, &first) in ambiguous.iter().enumerate() {
+        try_candidate(&[first])?;
+        for &second in &ambiguous[index + 1..] {
+            try_candidate(&[first, second])?;
+        }
+    }
+
+    found.ok_or(error)
+}
@@ -0,0 +1,176 @@
+//! Packed binary encoding of IBANs.
+
+use alloc::vec::Vec;
+
+use arrayvec::ArrayVec;
+
+use crate::{Case, Iban, ParseError, COUNTRIES, IBAN_MAX_LENGTH};
+
+/// How the characters after the country code are encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    /// Only digits, packed as base 10 numbers.
+    Decimal = 0,
+    /// Only digits and uppercase letters, packed as base 36 numbers.
+    Alphanumeric = 1,
+    /// Any other characters (such as lowercase letters in alphanumeric segments), as ASCII.
+    Ascii = 2,
+}
+
+impl Mode {
+    const fn base(self) -> u128 {
+        match self {
+            Self::Decimal => 10,
+            Self::Alphanumeric => 36,
+            Self::Ascii => 256,
+        }
+    }
+
+    /// The number of characters packed into each number, so that the number fits in a `u128`.
+    const fn chunk_len(self) -> usize {
+        match self {
+            Self::Decimal => 38,
+            Self::Alphanumeric => 24,
+            Self::Ascii => 1,
+        }
+    }
+}
+
+/// Returns the number of bytes needed to pack `len` characters with the given `base`.
+fn packed_len(base: u128, len: usize) -> usize {
+    #[allow(clippy::cast_possible_truncation)]
+    let max = base.pow(len as u32) - 1;
+    (128 - max.leading_zeros() as usize + 7) / 8
+}
+
+impl Iban {
+    /// Encode the IBAN in a packed binary form.
+    ///
+    /// The country code and the encoding are stored in the first two bytes. The check digits and
+    /// BBAN are then packed as base 10 numbers if they only contain digits, or as base 36 numbers
+    /// if they only contain digits and uppercase letters. Otherwise (if an alphanumeric segment
+    /// contains lowercase letters), they are stored as ASCII.
+    ///
+    /// As the length of the IBAN is determined by its country, it is not stored. A numeric German
+    /// IBAN is packed in 11 bytes, and no numeric IBAN needs more than 14 bytes. Use
+    /// [`Iban::unpack`] to decode the IBAN.
+    ///
+    /// ```
+    /// let iban: iban::Iban = "DE89370400440532013000".parse()?;
+    /// let packed = iban.pack();
+    /// assert_eq!(packed.len(), 11);
+    /// assert_eq!(iban::Iban::unpack(&packed), Ok(iban));
+    /// # Ok::<(), iban::ParseError>(())
+    /// ```
+    #[must_use]
+    pub fn pack(&self) -> Vec<u8> {
+        let (country_code, characters) = self.as_bytes().split_at(2);
+        let mode = if characters.iter().all(u8::is_ascii_digit) {
+            Mode::Decimal
+        } else if characters
+            .iter()
+            .all(|ch| ch.is_ascii_digit() || ch.is_ascii_uppercase())
+        {
+            Mode::Alphanumeric
+        } else {
+            Mode::Ascii
+        };
+
+        let country = u16::from(country_code[0] - b'A') * 26 + u16::from(country_code[1] - b'A');
+        let mut packed = Vec::new();
+        packed.extend_from_slice(&(country * 3 + mode as u16).to_be_bytes());
+
+        for chunk in characters.chunks(mode.chunk_len()) {
+            let value = chunk.iter().fold(0, |value, &ch| {
+                let digit = match mode {
+                    Mode::Ascii => ch,
+                    _ => char::from(ch)
+                        .to_digit(36)
+                        .expect("character is alphanumeric") as u8,
+                };
+                value * mode.base() + u128::from(digit)
+            });
+            let len = packed_len(mode.base(), chunk.len());
+            packed.extend_from_slice(&value.to_be_bytes()[16 - len..]);
+        }
+
+        packed
+    }
+
+    /// Decode an IBAN from the packed binary form produced by [`Iban::pack`].
+    ///
+    /// The decoded IBAN is validated as with [`Iban::parse_strict`].
+    ///
+    /// # Errors
+    /// This method returns a `ParseError` if the packed data is corrupted:
+    /// * The country code or encoding is invalid (see: `ParseError::CountryCode`)
+    /// * The country is unknown (see: `ParseError::UnknownCountry`)
+    /// * The data is truncated, or has trailing bytes (see: `ParseError::TooShort` and
+    ///   `ParseError::TooLong`, with the expected length of the IBAN)
+    /// * A packed number is out of range (see: `ParseError::InvalidCharacter`)
+    ///
+    /// Otherwise, it returns the same errors as [`Iban::parse_strict`] for the decoded IBAN.
+    pub fn unpack(bytes: &[u8]) -> Result<Self, ParseError> {
+        let (header, mut packed) = match bytes {
+            [first, second, rest @ ..] => (u16::from_be_bytes([*first, *second]), rest),
+            _ => return Err(ParseError::CountryCode),
+        };
+        let mode = match header % 3 {
+            0 => Mode::Decimal,
+            1 => Mode::Alphanumeric,
+            _ => Mode::Ascii,
+        };
+        let country = header / 3;
+        if country >= 26 * 26 {
+            return Err(ParseError::CountryCode);
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let country_code = [b'A' + (country / 26) as u8, b'A' + (country % 26) as u8];
+        let expected = core::str::from_utf8(&country_code)
+            .ok()
+            .and_then(|country_code| COUNTRIES.get(country_code))
+            .ok_or(ParseError::UnknownCountry)?
+            .length;
+
+        let mut characters = ArrayVec::<u8, IBAN_MAX_LENGTH>::new();
+        characters.extend(country_code);
+        let mut remaining = expected - 2;
+        while remaining > 0 {
+            let chunk_len = remaining.min(mode.chunk_len());
+            let len = packed_len(mode.base(), chunk_len);
+            if packed.len() < len {
+                return Err(ParseError::TooShort { expected });
+            }
+
+            let mut value = [0; 16];
+            value[16 - len..].copy_from_slice(&packed[..len]);
+            let mut value = u128::from_be_bytes(value);
+            packed = &packed[len..];
+
+            let start = characters.len();
+            for _ in 0..chunk_len {
+                #[allow(clippy::cast_possible_truncation)]
+                let digit = (value % mode.base()) as u8;
+                characters.push(match mode {
+                    Mode::Ascii => digit,
+                    _ => char::from_digit(digit.into(), 36)
+                        .expect("digit is below 36")
+                        .to_ascii_uppercase() as u8,
+                });
+                value /= mode.base();
+            }
+            if value != 0 {
+                return Err(ParseError::InvalidCharacter);
+            }
+            characters[start..].reverse();
+            remaining -= chunk_len;
+        }
+
+        if !packed.is_empty() {
+            return Err(ParseError::TooLong { expected });
+        }
+
+        Self::parse_bytes(characters.into_iter(), Case::Strict)
+    }
+}
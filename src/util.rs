@@ -17,6 +17,13 @@ pub trait IteratorExt: Iterator + Sized {
             needs_separator: false,
         }
     }
+
+    #[inline]
+    fn runs(self) -> Runs<Self> {
+        Runs {
+            iter: self.peekable(),
+        }
+    }
 }
 
 impl<I: Iterator> IteratorExt for I {}
@@ -75,6 +82,29 @@ where
     }
 }
 
+/// Run-length encodes an iterator, yielding `(count, item)` for each run of equal items.
+pub struct Runs<I: Iterator> {
+    iter: Peekable<I>,
+}
+
+impl<I: Iterator> Iterator for Runs<I>
+where
+    I::Item: PartialEq,
+{
+    type Item = (usize, I::Item);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let mut count = 1;
+        while self.iter.next_if_eq(&item).is_some() {
+            count += 1;
+        }
+
+        Some((count, item))
+    }
+}
+
 pub struct Chunks<'str, const N: usize>(&'str str);
 
 impl<'str, const N: usize> Iterator for Chunks<'str, N> {
@@ -92,6 +122,40 @@ impl<'str, const N: usize> Iterator for Chunks<'str, N> {
     }
 }
 
+/// Decodes a percent-encoded URL component, where `+` also encodes a space.
+///
+/// Malformed escapes are decoded as `u8::MAX`, which is not a valid IBAN character.
+pub struct PercentDecode<'str>(core::str::Bytes<'str>);
+
+impl<'str> PercentDecode<'str> {
+    #[inline]
+    pub fn new(s: &'str str) -> Self {
+        Self(s.bytes())
+    }
+}
+
+impl Iterator for PercentDecode<'_> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let hex = |byte: Option<u8>| char::from(byte?).to_digit(16);
+
+        match self.0.next()? {
+            b'%' => {
+                let (high, low) = (hex(self.0.next()), hex(self.0.next()));
+                Some(match (high, low) {
+                    #[allow(clippy::cast_possible_truncation)]
+                    (Some(high), Some(low)) => (high * 16 + low) as u8,
+                    _ => u8::MAX,
+                })
+            }
+            b'+' => Some(b' '),
+            byte => Some(byte),
+        }
+    }
+}
+
 pub fn digits(mut value: u8) -> impl Iterator<Item = u8> {
     let hundreds = value / 100;
     value -= hundreds * 100;
@@ -106,3 +170,40 @@ pub fn digits(mut value: u8) -> impl Iterator<Item = u8> {
         // Ensure at least one value (0) is provided by this iterator.
         .ensure_one(0)
 }
+
+/// Maps a full-width form of a printable ASCII character (U+FF01 to U+FF5E) to the ASCII character.
+///
+/// Any other character is returned unchanged.
+pub fn to_halfwidth(ch: char) -> char {
+    match ch {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(u32::from(ch) - 0xFEE0).unwrap_or(ch),
+        _ => ch,
+    }
+}
+
+/// Returns true if `ch` is a Unicode default-ignorable code point.
+///
+/// This includes zero-width characters, bidirectional control characters,
+/// variation selectors and the byte order mark.
+pub fn is_default_ignorable(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{061C}'
+            | '\u{115F}'..='\u{1160}'
+            | '\u{17B4}'..='\u{17B5}'
+            | '\u{180B}'..='\u{180F}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{206F}'
+            | '\u{3164}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FEFF}'
+            | '\u{FFA0}'
+            | '\u{FFF0}'..='\u{FFF8}'
+            | '\u{1BCA0}'..='\u{1BCA3}'
+            | '\u{1D173}'..='\u{1D17A}'
+            | '\u{E0000}'..='\u{E0FFF}'
+    )
+}
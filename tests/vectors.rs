@@ -0,0 +1,96 @@
+//! Conformance tests driven by the test vectors in `vectors.txt`.
+//!
+//! Each line of `vectors.txt` (after the header) is a `|` delimited record of
+//! `iban|expected_result|expected_country|expected_bank|expected_branch`.
+//!
+//! `expected_result` is either `Ok`, or the name of the expected `ParseError` variant.
+//! The remaining fields are only checked for `Ok` vectors, and an empty field means `None`.
+
+use iban::Iban;
+
+const VECTORS: &str = include_str!("vectors.txt");
+
+fn optional(field: &str) -> Option<&str> {
+    if field.is_empty() {
+        None
+    } else {
+        Some(field)
+    }
+}
+
+fn check(line: &str) -> Result<(), String> {
+    let fields = line.split('|').collect::<Vec<_>>();
+    let (input, expected_result, country, bank, branch) = match fields[..] {
+        [input, expected_result, country, bank, branch] => {
+            (input, expected_result, country, bank, branch)
+        }
+        _ => return Err(format!("malformed vector, expected 5 fields: {line:?}")),
+    };
+
+    let iban = match (Iban::parse(input), expected_result) {
+        (Ok(iban), "Ok") => iban,
+        (Ok(_), expected) => return Err(format!("{input:?}: expected {expected}, got Ok")),
+        (Err(err), "Ok") => return Err(format!("{input:?}: expected Ok, got {err:?}")),
+        (Err(err), expected) if format!("{err:?}") == expected => return Ok(()),
+        (Err(err), expected) => return Err(format!("{input:?}: expected {expected}, got {err:?}")),
+    };
+
+    let bban = iban.bban();
+    let mut problems = Vec::new();
+
+    if iban.country_code() != country {
+        problems.push(format!("country {:?} != {country:?}", iban.country_code()));
+    }
+    if bban.bank_identifier() != optional(bank) {
+        problems.push(format!(
+            "bank {:?} != {:?}",
+            bban.bank_identifier(),
+            optional(bank)
+        ));
+    }
+    if bban.branch_identifier() != optional(branch) {
+        problems.push(format!(
+            "branch {:?} != {:?}",
+            bban.branch_identifier(),
+            optional(branch)
+        ));
+    }
+    // Lowercase input is only kept in the segments that allow it, so the electronic format may
+    // differ from the input in case, but must be accepted as is by `Iban::parse_strict`.
+    if !iban.as_str().eq_ignore_ascii_case(input) {
+        problems.push(format!("electronic format {:?} != input", iban.as_str()));
+    }
+    if Iban::parse_strict(iban.as_str()) != Ok(iban) {
+        problems.push(format!(
+            "electronic format {:?} is not strict",
+            iban.as_str()
+        ));
+    }
+    let spaced = iban.to_string();
+    if Iban::parse(&spaced) != Ok(iban) {
+        problems.push(format!("display {spaced:?} does not round-trip"));
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{input:?}: {}", problems.join(", ")))
+    }
+}
+
+#[test]
+fn vectors() {
+    let failures = VECTORS
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| check(line).err())
+        .collect::<Vec<_>>();
+
+    assert!(
+        failures.is_empty(),
+        "{} vector(s) failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}